@@ -0,0 +1,84 @@
+"use strict";
+
+// Browser side of the game's platform helpers, loaded after mq_js_bundle.js and sapp_jsutils.js
+
+// The latest left to right tilt in degrees, whichever way round the screen is turned
+var cat_ball_wow_tilt_reading = NaN;
+
+window.addEventListener("deviceorientation", function (event) {
+    if (event.beta === null || event.gamma === null) {
+        return;
+    }
+    var angle = (screen.orientation && screen.orientation.angle) || window.orientation || 0;
+    switch ((angle + 360) % 360) {
+        case 90: cat_ball_wow_tilt_reading = event.beta; break;
+        case 180: cat_ball_wow_tilt_reading = -event.gamma; break;
+        case 270: cat_ball_wow_tilt_reading = -event.beta; break;
+        default: cat_ball_wow_tilt_reading = event.gamma;
+    }
+});
+
+// iOS only sends orientation events once asked from inside a touch
+document.addEventListener("touchend", function request_orientation() {
+    document.removeEventListener("touchend", request_orientation);
+    if (window.DeviceOrientationEvent && DeviceOrientationEvent.requestPermission) {
+        DeviceOrientationEvent.requestPermission().catch(function (e) {
+            console.warn("Could not get device orientation: " + e);
+        });
+    }
+});
+
+function register_plugin(importObject) {
+    importObject.env.cat_ball_wow_storage_get = function (key) {
+        try {
+            return js_object(window.localStorage.getItem(get_js_object(key)));
+        } catch (e) {
+            return js_object(null);
+        }
+    }
+
+    importObject.env.cat_ball_wow_storage_set = function (key, value) {
+        try {
+            window.localStorage.setItem(get_js_object(key), get_js_object(value));
+        } catch (e) {
+            console.warn("Could not save " + get_js_object(key) + ": " + e);
+        }
+    }
+
+    // Saves the bytes as a file through the browser's downloads
+    importObject.env.cat_ball_wow_download = function (name, data) {
+        var url = URL.createObjectURL(new Blob([get_js_object(data)], { type: "image/png" }));
+        var link = document.createElement("a");
+        link.href = url;
+        link.download = get_js_object(name);
+        link.click();
+        URL.revokeObjectURL(url);
+    }
+
+    // Not every browser can vibrate, those that can't just ignore it
+    importObject.env.cat_ball_wow_vibrate = function (milliseconds) {
+        if (navigator.vibrate) {
+            navigator.vibrate(milliseconds);
+        }
+    }
+
+    // Hidden tabs and windows in the background both count as not having focus
+    importObject.env.cat_ball_wow_has_focus = function () {
+        return document.hasFocus() && !document.hidden;
+    }
+
+    importObject.env.cat_ball_wow_tilt = function () {
+        return cat_ball_wow_tilt_reading;
+    }
+
+    // Lets a page embedding the game listen for scoring with
+    // window.addEventListener("cat_ball_wow_score", ...)
+    importObject.env.cat_ball_wow_score_event = function (kind, value) {
+        var kinds = ["score_changed", "combo_increased", "game_over"];
+        window.dispatchEvent(new CustomEvent("cat_ball_wow_score", {
+            detail: { kind: kinds[kind], value: value }
+        }));
+    }
+}
+
+miniquad_add_plugin({ register_plugin, version: 1, name: "cat_ball_wow" });
@@ -0,0 +1,161 @@
+use macroquad::prelude::*;
+
+use crate::ball::{play_events, Ball};
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::game_area::{Drawable, GameArea, Sprite};
+use crate::haptics::Haptics;
+use crate::input_state::InputState;
+use crate::layout::Layout;
+use crate::obstacle::Obstacle;
+use crate::paw::{Paw, PawController, PawSide};
+use crate::response_curve::ResponseCurve;
+use crate::score_popups::ScorePopups;
+use crate::scores::{ScoreMode, Scores};
+use crate::screen_shake::ScreenShake;
+use crate::slow_motion::SlowMotion;
+use crate::sounds::Sounds;
+use crate::sparks::Sparks;
+
+// Seconds without any input on the menu before the demo starts
+const ATTRACT_DELAY: f32 = 8.0;
+// How much the demo is faded into the background behind the menu
+const ATTRACT_FADE: f32 = 0.5;
+
+// A computer versus computer game played out behind the menu once it's been left alone for a
+// while. It plays practice so it goes on forever, with its own scores, sounds and effects so
+// nothing it does reaches the real game
+pub struct AttractMode {
+    ball_texture: Texture2D,
+    config: Config,
+    ball: Ball,
+    left_paw: Paw,
+    right_paw: Paw,
+    scores: Scores,
+    score_popups: ScorePopups,
+    sparks: Sparks,
+    sounds: Sounds,
+    screen_shake: ScreenShake,
+    haptics: Haptics,
+    slow_motion: SlowMotion,
+    // Seconds the menu has gone without input
+    idle_time: f32,
+}
+
+impl AttractMode {
+    pub fn new(textures: [Texture2D; 3], config: &Config, layout: Layout, sounds: &Sounds) -> Self {
+        let [ball_texture, left_paw_texture, right_paw_texture] = textures;
+        let difficulty = Difficulty::Normal;
+        let new_paw = |texture, paw_side| {
+            let mut paw = Paw::new(
+                texture,
+                paw_side,
+                config,
+                difficulty,
+                layout,
+                ResponseCurve::Linear,
+            );
+            paw.controller = PawController::Ai;
+            paw
+        };
+        let mut scores = Scores::new(difficulty, None);
+        scores.mode = ScoreMode::Practice;
+        Self {
+            ball: Ball::new(ball_texture.clone(), config, difficulty, layout),
+            ball_texture,
+            config: *config,
+            left_paw: new_paw(left_paw_texture, PawSide::Left),
+            right_paw: new_paw(right_paw_texture, PawSide::Right),
+            scores,
+            score_popups: ScorePopups::new(),
+            sparks: Sparks::new(),
+            sounds: sounds.silent(),
+            screen_shake: ScreenShake::new(),
+            haptics: Haptics::disabled(),
+            slow_motion: SlowMotion::new(),
+            idle_time: 0.0,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.idle_time >= ATTRACT_DELAY
+    }
+
+    // Counts towards starting the demo, and plays it once it has
+    pub fn update(&mut self, obstacles: &[Obstacle]) {
+        self.idle_time += get_frame_time();
+        if !self.is_running() {
+            return;
+        }
+        self.slow_motion.update();
+        let input = InputState {
+            delta: self.slow_motion.delta(),
+            frame_time: get_frame_time(),
+            ..Default::default()
+        };
+        let balls = std::slice::from_ref(&self.ball);
+        self.left_paw.update(&input, balls);
+        self.right_paw.update(&input, balls);
+        let events = self.ball.update(
+            &[&self.left_paw, &self.right_paw],
+            obstacles,
+            &input,
+            &mut self.scores,
+            &mut self.score_popups,
+            &mut self.sparks,
+            false,
+        );
+        play_events(
+            &events,
+            [&mut self.left_paw, &mut self.right_paw],
+            &mut self.sounds,
+            &mut self.haptics,
+            &mut self.screen_shake,
+            &mut self.slow_motion,
+        );
+        self.score_popups.update();
+        self.sparks.update(input.delta);
+    }
+
+    // Along the new edge, with a ball falling towards it
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.ball = Ball::new(
+            self.ball_texture.clone(),
+            &self.config,
+            Difficulty::Normal,
+            layout,
+        );
+        self.left_paw.set_layout(layout);
+        self.right_paw.set_layout(layout);
+    }
+
+    // Back to waiting out the delay, with the demo starting over next time
+    pub fn stop(&mut self) {
+        self.idle_time = 0.0;
+        self.ball.reset();
+        self.left_paw.reset();
+        self.right_paw.reset();
+        self.scores.reset();
+        self.score_popups.reset();
+        self.sparks.reset();
+        self.slow_motion.reset();
+    }
+}
+
+impl Drawable for AttractMode {
+    // Faded behind a wash of the overlay color so the menu stands out over it
+    fn draw(&self, game_area: &GameArea) {
+        self.left_paw.draw(game_area);
+        self.right_paw.draw(game_area);
+        self.ball.draw(game_area);
+        self.sparks.draw(game_area);
+        self.score_popups.draw(game_area);
+        draw_rectangle(
+            game_area.rect.x,
+            game_area.rect.y,
+            game_area.rect.w,
+            game_area.rect.h,
+            game_area.theme.overlay(ATTRACT_FADE),
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use std::collections::VecDeque;
+
+use macroquad::prelude::*;
+
+use crate::ball_physics::{BallEvent, BallPhysics, RunState, DYING_TIME, MAX_BALL_VELOCITY};
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::game_area::{GameArea, Sprite};
+use crate::haptics::Haptics;
+use crate::input_state::InputState;
+use crate::layout::Layout;
+use crate::obstacle::Obstacle;
+use crate::paw::{Paw, PawSide};
+use crate::score_popups::ScorePopups;
+use crate::scores::{ScoreMode, Scores};
+use crate::screen_shake::ScreenShake;
+use crate::slow_motion::SlowMotion;
+use crate::sounds::{Effect, Sounds};
+use crate::sparks::Sparks;
+use crate::theme::Theme;
+
+// Paw hits faster than this fraction of the top speed shake the screen
+const PAW_HIT_SHAKE_SPEED: f32 = 0.6;
+// Shake in game units at top speed, for a paw hit and for losing the ball
+const PAW_HIT_SHAKE: f32 = 1.0;
+const LOSE_SHAKE: f32 = 2.0;
+// The lost combo is shown this fraction of the playfield's height from the middle towards the
+// paws, kept this many game units in from the sides so its text fits
+const COMBO_BREAK_HEIGHT: f32 = 0.2;
+const COMBO_BREAK_MARGIN: f32 = 20.0;
+// Vibration lengths in milliseconds, kept short so they don't blur together
+const PAW_HIT_VIBRATION: u32 = 20;
+const LOSE_VIBRATION: u32 = 80;
+// Number of past positions kept for the trail, all of them are shown at top speed
+const TRAIL_LENGTH: usize = 12;
+const TRAIL_ALPHA: f32 = 0.4;
+// On a bounce the ball is drawn this much longer along its motion and thinner across it,
+// relaxing back to round at this rate per second
+const BOUNCE_SQUASH: f32 = 0.35;
+const SQUASH_RECOVERY: f32 = 3.0;
+
+// The ball as it's played and drawn, its motion being left to the physics
+pub struct Ball {
+    pub physics: BallPhysics,
+    texture: Texture2D,
+    config: Config,
+    layout: Layout,
+    // Most recent position first
+    trail: VecDeque<Vec2>,
+    // How deformed the ball is drawn, purely visual so the rect stays the same
+    squash: f32,
+}
+
+impl Ball {
+    pub fn new(
+        texture: Texture2D,
+        config: &Config,
+        difficulty: Difficulty,
+        layout: Layout,
+    ) -> Self {
+        Self {
+            physics: BallPhysics::new(config, difficulty, layout),
+            texture,
+            config: *config,
+            layout,
+            trail: VecDeque::with_capacity(TRAIL_LENGTH),
+            squash: 0.0,
+        }
+    }
+
+    // Steps the physics, scoring what the ball hit and showing it with popups and sparks.
+    // Returns what happened, for the game to play sounds and effects for
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        paws: &[&Paw],
+        obstacles: &[Obstacle],
+        input: &InputState,
+        scores: &mut Scores,
+        score_popups: &mut ScorePopups,
+        sparks: &mut Sparks,
+        steady_speed: bool,
+    ) -> Vec<BallEvent> {
+        let game_shape = self.config.game_shape;
+        let run = RunState {
+            score: scores.score,
+            mode: scores.mode,
+            steady_speed,
+            lives_left: scores.lives > 0,
+            safety_net: scores.safety_nets > 0,
+        };
+        let paws: Vec<_> = paws.iter().map(|paw| paw.body()).collect();
+        let moving = self.physics.in_flight();
+        self.squash = (self.squash - SQUASH_RECOVERY * input.delta).max(0.0);
+        let mut events = self.physics.step(&paws, obstacles, input, run);
+        if moving {
+            self.trail.push_front(self.physics.previous_position);
+            self.trail.truncate(TRAIL_LENGTH);
+        }
+
+        let mut wall_hit = false;
+        let mut combo_broken = false;
+        for event in &events {
+            match *event {
+                BallEvent::PawHit {
+                    paw_side, position, ..
+                } => {
+                    scores.add_points();
+                    score_popups.spawn(position, scores.combo);
+                    match paw_side {
+                        PawSide::Left => scores.left_hits += 1,
+                        PawSide::Right => scores.right_hits += 1,
+                    }
+                    scores.bump_combo(self.config.safety_net_combo);
+                    self.squash = BOUNCE_SQUASH;
+                }
+                BallEvent::WallHit { position, normal } => {
+                    sparks.spawn(position, normal);
+                    wall_hit = true;
+                }
+                BallEvent::ObstacleHit => self.squash = BOUNCE_SQUASH,
+                // The combo keeps going
+                BallEvent::Caught => {
+                    scores.catch_ball();
+                    self.squash = BOUNCE_SQUASH;
+                }
+                // Costs a life (except in practice) and the combo
+                BallEvent::Lost { .. } => {
+                    if scores.mode != ScoreMode::Practice {
+                        scores.lives = scores.lives.saturating_sub(1);
+                    }
+                    // A combo going is worth marking the end of, up off the edge where it can be
+                    // seen, except in zen which doesn't make a fuss over the score
+                    if scores.combo > 1 && scores.mode != ScoreMode::Zen {
+                        combo_broken = true;
+                        score_popups.spawn_combo_break(
+                            Vec2 {
+                                x: self
+                                    .physics
+                                    .rect
+                                    .center()
+                                    .x
+                                    .clamp(COMBO_BREAK_MARGIN, game_shape.x - COMBO_BREAK_MARGIN),
+                                y: game_shape.y / 2.0
+                                    + game_shape.y
+                                        * COMBO_BREAK_HEIGHT
+                                        * self.layout.towards_paws(),
+                            },
+                            scores.combo,
+                        );
+                    }
+                    scores.combo = 1;
+                    self.trail.clear();
+                }
+                BallEvent::ComboBroken => {}
+            }
+        }
+        // A corner is one bounce, though it's a hit on two walls
+        if wall_hit {
+            scores.add_points();
+            score_popups.spawn(self.physics.rect.center(), scores.combo);
+            self.squash = BOUNCE_SQUASH;
+        }
+        if combo_broken {
+            events.push(BallEvent::ComboBroken);
+        }
+        events
+    }
+
+    // Puts the ball down without any physics, for animations that script its path
+    pub fn place(&mut self, position: Vec2, velocity: Vec2) {
+        self.physics.place(position, velocity);
+    }
+
+    pub fn reset(&mut self) {
+        self.physics.reset();
+        self.trail.clear();
+        self.squash = 0.0;
+    }
+
+    // `alpha` is how far from its position before the last physics step to its position now
+    // it's drawn at, from 0 to 1
+    pub fn draw_interpolated(&self, game_area: &GameArea, alpha: f32) {
+        let physics = &self.physics;
+        let position = physics.previous_position.lerp(physics.rect.point(), alpha);
+        let ball_rect = Rect {
+            x: position.x,
+            y: position.y,
+            ..physics.rect
+        };
+        if let Some(dying) = physics.dying() {
+            let remaining = (dying / DYING_TIME).max(0.0);
+            let center = ball_rect.center();
+            let shrunk_rect = Rect {
+                x: center.x - ball_rect.w * remaining / 2.0,
+                y: center.y - ball_rect.h * remaining / 2.0,
+                w: ball_rect.w * remaining,
+                h: ball_rect.h * remaining,
+            };
+            self.draw_at(
+                game_area,
+                shrunk_rect,
+                Color {
+                    a: remaining,
+                    ..self.tint(game_area.theme)
+                },
+            );
+            return;
+        }
+
+        // The faster the ball goes the longer its trail, oldest copies being the most faded
+        let speed_fraction = (physics.velocity.length() / MAX_BALL_VELOCITY).min(1.0);
+        let trail_length =
+            ((TRAIL_LENGTH as f32 * speed_fraction).ceil() as usize).min(self.trail.len());
+        for (index, position) in self.trail.iter().take(trail_length).enumerate().rev() {
+            let alpha = TRAIL_ALPHA * (1.0 - (index + 1) as f32 / (trail_length + 1) as f32);
+            let rect = Rect {
+                x: position.x,
+                y: position.y,
+                ..physics.rect
+            };
+            self.draw_at(
+                game_area,
+                rect,
+                Color {
+                    a: alpha,
+                    ..self.tint(game_area.theme)
+                },
+            );
+        }
+        let stretch = 1.0 + self.squash;
+        let center = ball_rect.center();
+        let width = ball_rect.w * stretch;
+        let height = ball_rect.h / stretch;
+        let squashed_rect = Rect {
+            x: center.x - width / 2.0,
+            y: center.y - height / 2.0,
+            w: width,
+            h: height,
+        };
+        self.draw_at(game_area, squashed_rect, self.tint(game_area.theme));
+    }
+}
+
+// Plays the sounds, vibrations, shakes and slow motion for what the balls did in a step, and
+// flashes the paws that hit them
+pub fn play_events(
+    events: &[BallEvent],
+    paws: [&mut Paw; 2],
+    sounds: &mut Sounds,
+    haptics: &mut Haptics,
+    screen_shake: &mut ScreenShake,
+    slow_motion: &mut SlowMotion,
+) {
+    let [left_paw, right_paw] = paws;
+    for event in events {
+        match *event {
+            BallEvent::PawHit {
+                paw_side,
+                speed,
+                close_call,
+                ..
+            } => {
+                match paw_side {
+                    PawSide::Left => left_paw.flash(),
+                    PawSide::Right => right_paw.flash(),
+                }
+                sounds.play(Effect::Paw);
+                haptics.vibrate(PAW_HIT_VIBRATION);
+                if close_call {
+                    slow_motion.trigger();
+                }
+                let speed_fraction = speed / MAX_BALL_VELOCITY;
+                if speed_fraction > PAW_HIT_SHAKE_SPEED {
+                    screen_shake.shake(PAW_HIT_SHAKE * speed_fraction);
+                }
+            }
+            BallEvent::WallHit { .. } | BallEvent::ObstacleHit | BallEvent::Caught => {
+                sounds.play(Effect::Wall)
+            }
+            BallEvent::ComboBroken => sounds.play(Effect::ComboBreak),
+            BallEvent::Lost { speed } => {
+                sounds.play(Effect::Lose);
+                haptics.vibrate(LOSE_VIBRATION);
+                screen_shake.shake(LOSE_SHAKE * speed / MAX_BALL_VELOCITY);
+            }
+        }
+    }
+}
+
+impl Sprite for Ball {
+    fn rect(&self) -> Rect {
+        self.physics.rect
+    }
+
+    fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    fn tint(&self, theme: Theme) -> Color {
+        theme.ball_tint()
+    }
+
+    // Pointing along the velocity so the squash stretches the ball the way it's going, the ball
+    // being round this isn't visible otherwise
+    fn rotation(&self) -> f32 {
+        self.physics.velocity.y.atan2(self.physics.velocity.x)
+    }
+
+    // Where the last physics step left it
+    fn draw(&self, game_area: &GameArea) {
+        self.draw_interpolated(game_area, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ball_physics::tests::launched;
+    use crate::scores::tests::scores;
+
+    #[test]
+    fn ball_touching_a_wall_for_several_steps_scores_once() {
+        // Never drawn, so any texture id will do and nothing needs a window
+        let texture = Texture2D::from_miniquad_texture(miniquad::TextureId::from_raw_id(
+            miniquad::RawId::OpenGl(0),
+        ));
+        let mut ball = Ball::new(
+            texture,
+            &Config::default(),
+            Difficulty::Normal,
+            Layout::PawsAtBottom,
+        );
+        // Sliding slowly up the left wall, barely moving away from it once it's bounced
+        ball.physics = launched(Vec2::new(0.01, 50.0), Vec2::new(-0.5, -30.0));
+        let mut scores = scores(ScoreMode::Bounces);
+        let input = InputState {
+            delta: 1.0 / 60.0,
+            frame_time: 1.0 / 60.0,
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            ball.update(
+                &[],
+                &[],
+                &input,
+                &mut scores,
+                &mut ScorePopups::new(),
+                &mut Sparks::new(),
+                false,
+            );
+        }
+        assert_eq!(scores.score, 1);
+    }
+}
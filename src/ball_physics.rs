@@ -0,0 +1,844 @@
+use macroquad::prelude::*;
+
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::input_state::InputState;
+use crate::layout::Layout;
+use crate::obstacle::Obstacle;
+use crate::paw::PawSide;
+use crate::scores::ScoreMode;
+
+// The ball's speed creeps towards this as the score climbs but never reaches it. At a full
+// game width per second the ball is about as fast as the paws can keep up with
+pub const MAX_BALL_VELOCITY: f32 = 100.0;
+// The ball launches downwards at a random angle from vertical between these, towards either side
+const MIN_LAUNCH_ANGLE: f32 = std::f32::consts::PI / 12.0;
+const MAX_LAUNCH_ANGLE: f32 = std::f32::consts::PI / 4.0;
+// Every ball in a test build launches from this seed, since macroquad's RNG is shared by all the
+// tests running at once
+#[cfg(test)]
+const TEST_LAUNCH_SEED: u64 = 1;
+// Seconds the ball waits in the middle before launching
+const LAUNCH_COUNTDOWN: f32 = 3.0;
+// After launching, the ball eases from the starting speed up to its speed for the score over
+// this many seconds
+const LAUNCH_RAMP_TIME: f32 = 1.0;
+// Zen mode's ball drifts along at this fraction of the usual starting speed
+const ZEN_SPEED_SCALE: f32 = 0.75;
+// A paw hit with the ball's bottom this close to the bottom of the playfield is a last moment save,
+// and slows time down for a moment
+const CLOSE_CALL_HEIGHT: f32 = 14.0;
+// Seconds a lost ball takes to shrink and fade away at the bottom before it's gone
+pub const DYING_TIME: f32 = 0.6;
+// Steepest angle from vertical the ball can leave a paw at, when hitting the paw's very edge
+const MAX_BOUNCE_ANGLE: f32 = std::f32::consts::PI / 3.0;
+// A paw moving as it hits puts spin on the ball, curving it sideways the way the paw was moving
+// by up to MAX_SPIN game units per second squared, dying away at this rate per second
+const SPIN_PER_PAW_VELOCITY: f32 = 0.1;
+const MAX_SPIN: f32 = 15.0;
+const SPIN_DECAY: f32 = 1.5;
+// The fraction of a paw's sideways velocity the ball picks up when the paw hits it
+const PAW_MOMENTUM_TRANSFER: f32 = 0.15;
+
+// The part of a paw the ball bounces off
+#[derive(Clone, Copy)]
+pub struct PawBody {
+    pub rect: Rect,
+    // Sideways, in game units per second
+    pub velocity: f32,
+    pub paw_side: PawSide,
+}
+
+// What a step needs to know about the run it's part of
+#[derive(Clone, Copy)]
+pub struct RunState {
+    pub score: u32,
+    pub mode: ScoreMode,
+    pub steady_speed: bool,
+    // Whether a lost ball comes back once it's faded away
+    pub lives_left: bool,
+    // Whether a safety net is waiting to catch a ball falling off the paws' edge
+    pub safety_net: bool,
+}
+
+// Something that happened to the ball during a step, for the game to score, show and sound
+#[derive(Clone, Copy, PartialEq)]
+pub enum BallEvent {
+    // Where the ball's center was as it touched the paw, the speed for the score it leaves at,
+    // and whether it was a last moment save
+    PawHit {
+        paw_side: PawSide,
+        position: Vec2,
+        speed: f32,
+        close_call: bool,
+    },
+    // Where on the wall the ball touched it, and the way back into the field. A corner is a hit
+    // on both its walls
+    WallHit {
+        position: Vec2,
+        normal: Vec2,
+    },
+    ObstacleHit,
+    // A safety net threw the ball back from the paws' edge
+    Caught,
+    // A combo of more than one ended with the ball being lost, not from a step but from scoring it
+    ComboBroken,
+    // The ball fell off the paws' edge, going at the speed for the score
+    Lost {
+        speed: f32,
+    },
+}
+
+// The ball's motion, and what it bounces off, with nothing drawn or played so it can be stepped
+// without a window
+pub struct BallPhysics {
+    pub rect: Rect,
+    // Where the ball was before the last step, to draw it part way from
+    pub previous_position: Vec2,
+    pub velocity: Vec2,
+    config: Config,
+    difficulty: Difficulty,
+    layout: Layout,
+    countdown: f32,
+    // Seconds the ball has been moving since it launched, up to the ramp time
+    launch_ramp: f32,
+    // The paw that hit the ball last, if any has since it launched
+    pub last_paw: Option<PawSide>,
+    // Seconds left of the lost animation, once the ball has fallen off the paws' edge
+    dying: Option<f32>,
+    // Sideways acceleration from the last paw hit
+    spin: f32,
+    // Speed the last paw hit added to the speed for the score, or took off it, kept through
+    // later bounces until the next hit
+    boost: f32,
+    // State of the ball's own generator for launch directions, seeded from macroquad's RNG so a
+    // recorded run still launches the same way when it's played back
+    launch_rng: u64,
+}
+
+impl BallPhysics {
+    pub fn new(config: &Config, difficulty: Difficulty, layout: Layout) -> Self {
+        let mut physics = Self {
+            rect: Rect {
+                w: config.ball_shape.x * difficulty.ball_scale(),
+                h: config.ball_shape.y * difficulty.ball_scale(),
+                ..Default::default()
+            },
+            previous_position: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            config: *config,
+            difficulty,
+            layout,
+            countdown: 0.0,
+            launch_ramp: 0.0,
+            last_paw: None,
+            dying: None,
+            spin: 0.0,
+            boost: 0.0,
+            launch_rng: launch_seed(),
+        };
+        physics.reset();
+        physics
+    }
+
+    // Moves the ball on by the input's delta, bouncing it off the paws, obstacles and walls, and
+    // returns what it hit
+    pub fn step(
+        &mut self,
+        paws: &[PawBody],
+        obstacles: &[Obstacle],
+        input: &InputState,
+        run: RunState,
+    ) -> Vec<BallEvent> {
+        let delta = input.delta;
+        let game_shape = self.config.game_shape;
+        let mut events = Vec::new();
+        self.previous_position = self.rect.point();
+        let base_ball_velocity = if run.mode == ScoreMode::Zen {
+            self.config.base_ball_velocity * ZEN_SPEED_SCALE
+        } else {
+            self.config.base_ball_velocity
+        };
+
+        // A lost ball plays out its animation, then comes back to the middle if there are lives left
+        if let Some(dying) = self.dying.as_mut() {
+            *dying -= delta;
+            if *dying <= 0.0 && run.lives_left {
+                self.reset();
+            }
+            return events;
+        }
+
+        // Hold the ball still until the countdown runs out, paws can still move meanwhile
+        if self.countdown > 0.0 {
+            self.countdown -= delta;
+            return events;
+        }
+
+        // calculate ball velocity, practice, zen and the steady speed option keep it at the
+        // starting speed, though the score still counts up as usual
+        let steady = matches!(run.mode, ScoreMode::Practice | ScoreMode::Zen) || run.steady_speed;
+        let score_ball_velocity = if steady {
+            base_ball_velocity
+        } else {
+            ball_speed(
+                base_ball_velocity,
+                run.score,
+                self.difficulty.speed_growth(),
+            )
+        };
+        // Relaunching after a long rally doesn't jump straight to its speed, the ball picks up
+        // from the starting speed instead
+        let ramping = self.launch_ramp < LAUNCH_RAMP_TIME;
+        self.launch_ramp = (self.launch_ramp + delta).min(LAUNCH_RAMP_TIME);
+        let ball_velocity = base_ball_velocity
+            + (score_ball_velocity - base_ball_velocity) * self.launch_ramp / LAUNCH_RAMP_TIME;
+        let boosted_velocity = (ball_velocity + self.boost).min(MAX_BALL_VELOCITY);
+        if ramping {
+            self.velocity = self.velocity.normalize_or_zero() * boosted_velocity;
+        }
+        // Spin curves the ball without changing its speed
+        if self.spin != 0.0 {
+            let speed = self.velocity.length();
+            self.velocity.x += self.spin * delta;
+            self.velocity = self.velocity.normalize_or_zero() * speed;
+            self.spin *= (-SPIN_DECAY * delta).exp();
+        }
+
+        // Check for collision with paws along the whole path travelled this frame,
+        // so a fast ball can't skip over a paw between two frames. Any overlap counts as a hit,
+        // but only while falling towards them so the ball can't bounce twice off the same paw
+        let towards_paws = self.layout.towards_paws();
+        let movement = self.velocity * delta;
+        let mut travel = 1.0;
+        let paw_hit = paws
+            .iter()
+            .filter(|_| self.velocity.y * towards_paws > 0.0)
+            .filter_map(|paw| {
+                if self.rect.overlaps(&paw.rect) {
+                    Some((paw, 0.0))
+                } else {
+                    swept_collision(self.rect, movement, paw.rect).map(|hit_time| (paw, hit_time))
+                }
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((paw, hit_time)) = paw_hit {
+            let paw_location = paw.rect;
+            // Stop the ball where it touched the paw and send it back away, angled away from
+            // the paw's center the further off-center it landed
+            travel = hit_time;
+            let offset = (paw_location.center().x - self.rect.center().x)
+                / ((paw_location.w + self.rect.w) / 2.0);
+            let bounce_angle = offset.clamp(-1.0, 1.0) * MAX_BOUNCE_ANGLE;
+            self.velocity = Vec2 {
+                x: -bounce_angle.sin(),
+                y: -bounce_angle.cos() * towards_paws,
+            } * ball_velocity;
+            // A paw swung into the ball knocks it sideways harder, though never past top speed
+            self.velocity.x += paw.velocity * PAW_MOMENTUM_TRANSFER;
+            self.velocity = self.velocity.clamp_length_max(MAX_BALL_VELOCITY);
+            self.last_paw = Some(paw.paw_side);
+            self.boost = self.velocity.length() - ball_velocity;
+            self.spin = (paw.velocity * SPIN_PER_PAW_VELOCITY).clamp(-MAX_SPIN, MAX_SPIN);
+            let hit_y = self.rect.y + movement.y * hit_time;
+            let height_above_edge = match self.layout {
+                Layout::PawsAtBottom => game_shape.y - (hit_y + self.rect.h),
+                Layout::PawsAtTop => hit_y,
+            };
+            events.push(BallEvent::PawHit {
+                paw_side: paw.paw_side,
+                position: self.rect.center() + movement * hit_time,
+                speed: ball_velocity,
+                close_call: height_above_edge < CLOSE_CALL_HEIGHT,
+            });
+        }
+
+        // Update position
+        self.rect.x += movement.x * travel;
+        self.rect.y += movement.y * travel;
+
+        for obstacle in obstacles {
+            if self.bounce_off(obstacle.rect) {
+                events.push(BallEvent::ObstacleHit);
+            }
+        }
+
+        // Check for collision with walls after moving, and put the ball back against any wall it
+        // went through so it's never drawn poking out, even when it hits a corner. Only a ball
+        // heading into a wall bounces and scores, so one that stays touching it (say, pressed
+        // there by an obstacle) scores once per contact. Spin turns round with the ball off the
+        // side walls, so it keeps curving away from them
+        let mut wall_hit = false;
+        if self.rect.x < 0.0 {
+            self.rect.x = 0.0;
+            if self.velocity.x < 0.0 {
+                self.velocity.x = -self.velocity.x;
+                self.spin = -self.spin;
+                wall_hit = true;
+                events.push(BallEvent::WallHit {
+                    position: Vec2 {
+                        x: 0.0,
+                        y: self.rect.center().y,
+                    },
+                    normal: Vec2::X,
+                });
+            }
+        }
+        if self.rect.right() > game_shape.x {
+            self.rect.x = game_shape.x - self.rect.w;
+            if self.velocity.x > 0.0 {
+                self.velocity.x = -self.velocity.x;
+                self.spin = -self.spin;
+                wall_hit = true;
+                events.push(BallEvent::WallHit {
+                    position: Vec2 {
+                        x: game_shape.x,
+                        y: self.rect.center().y,
+                    },
+                    normal: Vec2::NEG_X,
+                });
+            }
+        }
+        // The last wall is the edge across from the paws
+        match self.layout {
+            Layout::PawsAtBottom if self.rect.y < 0.0 => {
+                self.rect.y = 0.0;
+                if self.velocity.y < 0.0 {
+                    self.velocity.y = -self.velocity.y;
+                    wall_hit = true;
+                    events.push(BallEvent::WallHit {
+                        position: Vec2 {
+                            x: self.rect.center().x,
+                            y: 0.0,
+                        },
+                        normal: Vec2::Y,
+                    });
+                }
+            }
+            Layout::PawsAtTop if self.rect.bottom() > game_shape.y => {
+                self.rect.y = game_shape.y - self.rect.h;
+                if self.velocity.y > 0.0 {
+                    self.velocity.y = -self.velocity.y;
+                    wall_hit = true;
+                    events.push(BallEvent::WallHit {
+                        position: Vec2 {
+                            x: self.rect.center().x,
+                            y: game_shape.y,
+                        },
+                        normal: Vec2::NEG_Y,
+                    });
+                }
+            }
+            _ => {}
+        }
+        if wall_hit {
+            self.velocity = self.velocity.normalize_or_zero() * boosted_velocity;
+        }
+
+        // Falling off the paws' edge leaves the ball sitting on the edge while it fades away,
+        // unless a safety net throws it back
+        let fell_off = match self.layout {
+            Layout::PawsAtBottom => self.rect.y > game_shape.y,
+            Layout::PawsAtTop => self.rect.bottom() < 0.0,
+        };
+        if fell_off {
+            self.rect.y = match self.layout {
+                Layout::PawsAtBottom => game_shape.y - self.rect.h,
+                Layout::PawsAtTop => 0.0,
+            };
+            if run.safety_net {
+                self.velocity.y = -self.velocity.y;
+                events.push(BallEvent::Caught);
+            } else {
+                self.velocity = Vec2::ZERO;
+                self.dying = Some(DYING_TIME);
+                events.push(BallEvent::Lost {
+                    speed: ball_velocity,
+                });
+            }
+        }
+
+        events
+    }
+
+    // Pushes the ball out of `rect` along whichever axis it's least far into it and reflects it
+    // away on that axis, so it can't get stuck inside even when the obstacle moves into it.
+    // Returns whether it was overlapping
+    fn bounce_off(&mut self, rect: Rect) -> bool {
+        let Some(overlap) = self.rect.intersect(rect) else {
+            return false;
+        };
+        if overlap.w < overlap.h {
+            if self.rect.center().x < rect.center().x {
+                self.rect.x -= overlap.w;
+                self.velocity.x = -self.velocity.x.abs();
+            } else {
+                self.rect.x += overlap.w;
+                self.velocity.x = self.velocity.x.abs();
+            }
+        } else if self.rect.center().y < rect.center().y {
+            self.rect.y -= overlap.h;
+            self.velocity.y = -self.velocity.y.abs();
+        } else {
+            self.rect.y += overlap.h;
+            self.velocity.y = self.velocity.y.abs();
+        }
+        true
+    }
+
+    // Whether the ball's moving, having launched and not been lost
+    pub fn in_flight(&self) -> bool {
+        self.dying.is_none() && self.countdown <= 0.0
+    }
+
+    // Seconds left of the lost animation, if the ball's been lost
+    pub fn dying(&self) -> Option<f32> {
+        self.dying
+    }
+
+    // Where the ball's center will be along x when it comes down to `paw_line`, following its
+    // current velocity and bouncing off the side walls, or nothing if it isn't heading there.
+    // Spin and obstacles aren't accounted for
+    pub fn predict_landing(&self, paw_line: f32) -> Option<f32> {
+        if self.dying.is_some() {
+            return None;
+        }
+        let distance = match self.layout {
+            Layout::PawsAtBottom => paw_line - self.rect.bottom(),
+            Layout::PawsAtTop => self.rect.y - paw_line,
+        };
+        let speed_towards_paws = self.velocity.y * self.layout.towards_paws();
+        if distance < 0.0 || speed_towards_paws <= 0.0 {
+            return None;
+        }
+        let time = distance / speed_towards_paws;
+        // Fold the straight line path back into the space the center can move in, once per wall
+        // it would bounce off
+        let min_x = self.rect.w / 2.0;
+        let width = self.config.game_shape.x - self.rect.w;
+        if width <= 0.0 {
+            return Some(self.rect.center().x);
+        }
+        let unfolded = self.rect.center().x + self.velocity.x * time - min_x;
+        let folded = unfolded.rem_euclid(width * 2.0);
+        Some(min_x + width - (folded - width).abs())
+    }
+
+    // Whether the ball's in play, heading for the paws' edge and less than `danger_height` from
+    // falling off it
+    pub fn in_danger(&self, danger_height: f32) -> bool {
+        let height_above_edge = match self.layout {
+            Layout::PawsAtBottom => self.config.game_shape.y - self.rect.bottom(),
+            Layout::PawsAtTop => self.rect.y,
+        };
+        self.in_flight()
+            && self.velocity.y * self.layout.towards_paws() > 0.0
+            && height_above_edge < danger_height
+    }
+
+    // The number to show while waiting to launch
+    pub fn countdown_number(&self) -> Option<u32> {
+        (self.countdown > 0.0).then(|| self.countdown.ceil() as u32)
+    }
+
+    // Puts the ball down without any physics, for animations that script its path
+    pub fn place(&mut self, position: Vec2, velocity: Vec2) {
+        self.rect.x = position.x;
+        self.rect.y = position.y;
+        self.previous_position = position;
+        self.velocity = velocity;
+    }
+
+    // Only once the lost animation has finished
+    pub fn is_lost(&self) -> bool {
+        self.dying.is_some_and(|dying| dying <= 0.0)
+    }
+
+    pub fn reset(&mut self) {
+        let game_shape = self.config.game_shape;
+        self.rect.x = game_shape.x / 2.0 - self.rect.w / 2.0;
+        self.rect.y = game_shape.y / 2.0 - self.rect.h / 2.0;
+        // Jumps straight back rather than being drawn sliding over from where it was lost
+        self.previous_position = self.rect.point();
+        self.countdown = LAUNCH_COUNTDOWN;
+        self.launch_ramp = 0.0;
+        self.last_paw = None;
+        self.spin = 0.0;
+        self.boost = 0.0;
+        self.dying = None;
+        let mut launch_angle =
+            MIN_LAUNCH_ANGLE + (MAX_LAUNCH_ANGLE - MIN_LAUNCH_ANGLE) * self.next_random();
+        if self.next_random() < 0.5 {
+            launch_angle = -launch_angle;
+        }
+        self.velocity = Vec2 {
+            x: launch_angle.sin(),
+            y: launch_angle.cos() * self.layout.towards_paws(),
+        } * self.config.base_ball_velocity;
+    }
+
+    // The next number from the launch generator, from 0 up to 1, with the same step as
+    // macroquad's RNG
+    fn next_random(&mut self) -> f32 {
+        self.launch_rng = self
+            .launch_rng
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.launch_rng >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[cfg(not(test))]
+fn launch_seed() -> u64 {
+    rand::rand() as u64
+}
+
+#[cfg(test)]
+fn launch_seed() -> u64 {
+    TEST_LAUNCH_SEED
+}
+
+// Speed for the given score, growing by 1% of the base speed per point at first (scaled by
+// `growth`) and then easing off so it approaches MAX_BALL_VELOCITY smoothly instead of hitting a wall
+fn ball_speed(base_ball_velocity: f32, score: u32, growth: f32) -> f32 {
+    let headroom = MAX_BALL_VELOCITY - base_ball_velocity;
+    if headroom <= 0.0 {
+        return MAX_BALL_VELOCITY;
+    }
+    let growth_scale = headroom * 100.0 / (base_ball_velocity * growth);
+    base_ball_velocity + headroom * (1.0 - (-((score + 1) as f32) / growth_scale).exp())
+}
+
+// Returns the fraction of `movement` at which `rect` first touches `target`, if it does this frame
+fn swept_collision(rect: Rect, movement: Vec2, target: Rect) -> Option<f32> {
+    // Grow the target by the size of the moving rect so the moving rect can be treated as a point
+    let expanded = Rect {
+        x: target.x - rect.w,
+        y: target.y - rect.h,
+        w: target.w + rect.w,
+        h: target.h + rect.h,
+    };
+
+    let mut entry_time = f32::NEG_INFINITY;
+    let mut exit_time = f32::INFINITY;
+    for (start, distance, min, max) in [
+        (rect.x, movement.x, expanded.left(), expanded.right()),
+        (rect.y, movement.y, expanded.top(), expanded.bottom()),
+    ] {
+        if distance == 0.0 {
+            // Not moving on this axis, so it has to already be inside the target's span
+            if start <= min || start >= max {
+                return None;
+            }
+        } else {
+            let near_time = (min - start) / distance;
+            let far_time = (max - start) / distance;
+            entry_time = entry_time.max(near_time.min(far_time));
+            exit_time = exit_time.min(near_time.max(far_time));
+        }
+    }
+
+    if entry_time < exit_time && (0.0..=1.0).contains(&entry_time) {
+        Some(entry_time)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const STEP: f32 = 1.0 / 60.0;
+
+    fn input(delta: f32) -> InputState {
+        InputState {
+            delta,
+            frame_time: delta,
+            ..Default::default()
+        }
+    }
+
+    fn run() -> RunState {
+        RunState {
+            score: 0,
+            mode: ScoreMode::Bounces,
+            steady_speed: false,
+            lives_left: true,
+            safety_net: false,
+        }
+    }
+
+    // A ball past its countdown and launch ramp, at `position` going at `velocity`
+    pub fn launched(position: Vec2, velocity: Vec2) -> BallPhysics {
+        let mut ball =
+            BallPhysics::new(&Config::default(), Difficulty::Normal, Layout::PawsAtBottom);
+        ball.countdown = 0.0;
+        ball.launch_ramp = LAUNCH_RAMP_TIME;
+        ball.place(position, velocity);
+        ball
+    }
+
+    // A paw along the bottom of the default playfield with its left edge at `x`
+    fn paw(x: f32, velocity: f32) -> PawBody {
+        PawBody {
+            rect: Rect::new(x, 80.0, 13.0, 20.0),
+            velocity,
+            paw_side: if x < 50.0 {
+                PawSide::Left
+            } else {
+                PawSide::Right
+            },
+        }
+    }
+
+    // Paws that are always waiting under where the ball will land, each kept to its own half
+    fn tracking_paws(ball: &BallPhysics) -> Vec<PawBody> {
+        let landing = ball.predict_landing(80.0).unwrap_or(50.0);
+        let left_x = (landing - 6.5).clamp(0.0, 37.0);
+        let right_x = (landing - 6.5).clamp(50.0, 87.0);
+        vec![paw(left_x, 0.0), paw(right_x, 0.0)]
+    }
+
+    #[test]
+    fn fresh_balls_launch_the_same_way_every_reset() {
+        let new_ball =
+            || BallPhysics::new(&Config::default(), Difficulty::Normal, Layout::PawsAtBottom);
+        let (mut first, mut second) = (new_ball(), new_ball());
+        for _ in 0..5 {
+            assert_eq!(first.velocity, second.velocity);
+            // Downwards towards the paws at the starting speed
+            assert!(first.velocity.y > 0.0);
+            let speed = first.velocity.length();
+            assert!((speed - Config::default().base_ball_velocity).abs() < 1e-3);
+            first.reset();
+            second.reset();
+        }
+    }
+
+    #[test]
+    fn tracking_paws_keep_a_rally_going() {
+        let mut ball = launched(Vec2::new(45.0, 45.0), Vec2::new(20.0, 30.0));
+        let mut run = run();
+        let mut paw_hits = 0;
+        for _ in 0..60 * 60 {
+            let paws = tracking_paws(&ball);
+            for event in ball.step(&paws, &[], &input(STEP), run) {
+                match event {
+                    BallEvent::PawHit { .. } => {
+                        paw_hits += 1;
+                        run.score += 1;
+                    }
+                    BallEvent::WallHit { .. } => run.score += 1,
+                    BallEvent::Lost { .. } => panic!("lost the ball after {} paw hits", paw_hits),
+                    _ => {}
+                }
+            }
+        }
+        assert!(paw_hits >= 10, "only {} paw hits", paw_hits);
+    }
+
+    #[test]
+    fn missed_ball_is_lost_once_then_relaunches() {
+        let mut ball = launched(Vec2::new(45.0, 45.0), Vec2::new(0.0, 30.0));
+        let mut lost = 0;
+        for _ in 0..60 * 3 {
+            let events = ball.step(&[], &[], &input(STEP), run());
+            lost += events
+                .iter()
+                .filter(|event| matches!(event, BallEvent::Lost { .. }))
+                .count();
+        }
+        assert_eq!(lost, 1);
+        assert!(!ball.is_lost());
+        assert_eq!(ball.countdown_number(), Some(3));
+    }
+
+    #[test]
+    fn lost_ball_stays_lost_without_lives() {
+        let mut ball = launched(Vec2::new(45.0, 85.0), Vec2::new(0.0, 30.0));
+        let run = RunState {
+            lives_left: false,
+            ..run()
+        };
+        for _ in 0..60 * 3 {
+            ball.step(&[], &[], &input(STEP), run);
+        }
+        assert!(ball.is_lost());
+    }
+
+    #[test]
+    fn safety_net_throws_the_ball_back() {
+        let mut ball = launched(Vec2::new(45.0, 99.5), Vec2::new(0.0, 60.0));
+        let run = RunState {
+            safety_net: true,
+            ..run()
+        };
+        let events = ball.step(&[], &[], &input(STEP), run);
+        assert!(events.contains(&BallEvent::Caught));
+        assert!(ball.in_flight());
+        assert!(ball.velocity.y < 0.0);
+    }
+
+    #[test]
+    fn fast_ball_bounces_off_a_paw_instead_of_tunnelling() {
+        // Further in one step than the whole playfield is tall
+        let mut ball = launched(Vec2::new(45.0, 10.0), Vec2::new(0.0, 150.0 / STEP));
+        let events = ball.step(&[paw(43.0, 0.0)], &[], &input(STEP), run());
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, BallEvent::PawHit { .. })));
+        assert!(ball.velocity.y < 0.0);
+        assert!(ball.rect.bottom() <= 80.0 + 0.001);
+    }
+
+    #[test]
+    fn ball_overlapping_a_paws_edge_bounces_only_while_heading_into_it() {
+        // Just over the paw's left edge, nowhere near covering its center
+        let position = Vec2::new(34.0, 75.0);
+        let paws = [paw(43.0, 0.0)];
+
+        let mut falling = launched(position, Vec2::new(0.0, 30.0));
+        let events = falling.step(&paws, &[], &input(STEP), run());
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, BallEvent::PawHit { .. })));
+        assert!(falling.velocity.y < 0.0);
+
+        let mut rising = launched(position, Vec2::new(0.0, -30.0));
+        let events = rising.step(&paws, &[], &input(STEP), run());
+        assert!(events.is_empty());
+        assert_eq!(rising.velocity, Vec2::new(0.0, -30.0));
+    }
+
+    #[test]
+    fn ball_ends_up_in_the_same_place_at_any_frame_rate() {
+        let start = Vec2::new(20.0, 20.0);
+        let velocity = Vec2::new(30.0, 20.0);
+        let mut once = launched(start, velocity);
+        let mut twice = launched(start, velocity);
+        for _ in 0..30 {
+            once.step(&[], &[], &input(2.0 * STEP), run());
+        }
+        for _ in 0..60 {
+            twice.step(&[], &[], &input(STEP), run());
+        }
+        assert!(once.rect.point().distance(twice.rect.point()) < 0.01);
+    }
+
+    #[test]
+    fn ball_speed_grows_with_score_up_to_the_cap() {
+        let base = Config::default().base_ball_velocity;
+        for difficulty in Difficulty::ALL {
+            let growth = difficulty.speed_growth();
+            let mut last = ball_speed(base, 0, growth);
+            assert!(last >= base);
+            for score in (1..10_000).chain([100_000, u32::MAX - 1]) {
+                let speed = ball_speed(base, score, growth);
+                assert!(speed >= last, "slower at a score of {}", score);
+                assert!(
+                    speed <= MAX_BALL_VELOCITY,
+                    "too fast at a score of {}",
+                    score
+                );
+                last = speed;
+            }
+            assert!(ball_speed(base, 100, growth) > ball_speed(base, 0, growth));
+        }
+    }
+
+    #[test]
+    fn ball_driven_into_a_corner_bounces_off_both_walls_and_stays_inside() {
+        for (position, velocity) in [
+            (Vec2::new(5.0, 5.0), Vec2::new(-3000.0, -3000.0)),
+            (Vec2::new(85.0, 5.0), Vec2::new(3000.0, -3000.0)),
+        ] {
+            let mut ball = launched(position, velocity);
+            let events = ball.step(&[], &[], &input(STEP), run());
+            let wall_hits = events
+                .iter()
+                .filter(|event| matches!(event, BallEvent::WallHit { .. }))
+                .count();
+            assert_eq!(wall_hits, 2);
+            assert!(ball.rect.left() >= 0.0 && ball.rect.right() <= 100.0);
+            assert!(ball.rect.top() >= 0.0);
+            assert_eq!(ball.velocity.x.signum(), -velocity.x.signum());
+            assert_eq!(ball.velocity.y.signum(), -velocity.y.signum());
+        }
+    }
+
+    #[test]
+    fn ball_touching_a_wall_for_several_steps_hits_it_once() {
+        // Sliding up the left wall, barely moving away from it once it's bounced
+        let mut ball = launched(Vec2::new(0.01, 50.0), Vec2::new(-0.5, -30.0));
+        let mut steps_with_hits = 0;
+        for _ in 0..10 {
+            let events = ball.step(&[], &[], &input(STEP), run());
+            if events
+                .iter()
+                .any(|event| matches!(event, BallEvent::WallHit { .. }))
+            {
+                steps_with_hits += 1;
+            }
+            assert!(ball.rect.x < 0.2, "left the wall");
+        }
+        assert_eq!(steps_with_hits, 1);
+    }
+
+    // How far sideways of the straight line it left the paw along the ball is half a second after
+    // being hit by a paw moving at `paw_velocity`
+    fn curve_after_hit(paw_velocity: f32) -> f32 {
+        let mut ball = launched(Vec2::new(45.0, 69.0), Vec2::new(0.0, 60.0));
+        ball.step(&[paw(43.0, paw_velocity)], &[], &input(STEP), run());
+        let start = ball.rect.point();
+        let velocity = ball.velocity;
+        for _ in 0..30 {
+            ball.step(&[], &[], &input(STEP), run());
+        }
+        let straight = start + velocity * 30.0 * STEP;
+        ball.rect.x - straight.x
+    }
+
+    #[test]
+    fn spin_from_a_moving_paw_curves_the_ball() {
+        assert!(curve_after_hit(0.0).abs() < 0.001);
+        // Curving the way the paw was moving
+        assert!(curve_after_hit(100.0) > 0.5);
+        assert!(curve_after_hit(-100.0) < -0.5);
+    }
+
+    // The ball's speed just after a paw moving at `paw_velocity` hits it straight on, and once it's
+    // since bounced off the far wall, all while it's still picking up speed after launching
+    fn speeds_after_hit(paw_velocity: f32) -> (f32, f32) {
+        let mut ball = launched(Vec2::new(45.0, 69.8), Vec2::new(0.0, 60.0));
+        ball.launch_ramp = 0.0;
+        let run = RunState { score: 50, ..run() };
+        ball.step(&[paw(43.0, paw_velocity)], &[], &input(STEP), run);
+        let after_hit = ball.velocity.length();
+        for _ in 0..60 * 3 {
+            let events = ball.step(&[], &[], &input(STEP), run);
+            if events
+                .iter()
+                .any(|event| matches!(event, BallEvent::WallHit { .. }))
+            {
+                return (after_hit, ball.velocity.length());
+            }
+        }
+        panic!("never reached a wall");
+    }
+
+    #[test]
+    fn moving_paw_sends_the_ball_off_faster_than_a_still_one() {
+        let mut still = launched(Vec2::new(45.0, 69.0), Vec2::new(0.0, 60.0));
+        still.step(&[paw(42.5, 0.0)], &[], &input(STEP), run());
+        let mut moving = launched(Vec2::new(45.0, 69.0), Vec2::new(0.0, 60.0));
+        moving.step(&[paw(42.5, 100.0)], &[], &input(STEP), run());
+        assert!(moving.velocity.x.abs() > still.velocity.x.abs() + 1.0);
+
+        // And keeps the extra speed through the launch ramp and off the walls
+        let (still_hit, still_wall) = speeds_after_hit(0.0);
+        let (moving_hit, moving_wall) = speeds_after_hit(100.0);
+        assert!(moving_hit > still_hit);
+        assert!(moving_wall > still_wall + 0.5);
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+use nanoserde::{Toml, TomlParser};
+
+use crate::input_state::TouchStrategy;
+
+const CONFIG_PATH: &str = "game_config.toml";
+
+// Tuning values that playtesters can override from game_config.toml without recompiling
+#[derive(Clone, Copy)]
+pub struct Config {
+    // Paw acceleration and friction are in game units per second squared
+    pub paw_acceleration: f32,
+    pub paw_friction: f32,
+    pub ball_shape: Vec2,
+    // Ball velocity is the ball's speed in game units per second
+    pub base_ball_velocity: f32,
+    pub game_shape: Vec2,
+    // How a paw picks between several touches on its half
+    pub touch_strategy: TouchStrategy,
+    // Every this many combo earns a safety net across the paws' edge, 0 never does
+    pub safety_net_combo: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            paw_acceleration: 18000.0,
+            paw_friction: -12.0,
+            ball_shape: Vec2 { x: 10.0, y: 10.0 },
+            base_ball_velocity: 34.0,
+            game_shape: Vec2 { x: 100.0, y: 100.0 },
+            touch_strategy: TouchStrategy::Nearest,
+            safety_net_combo: 10,
+        }
+    }
+}
+
+impl Config {
+    // Falls back to the defaults if the file is missing or can't be parsed
+    pub async fn load() -> Self {
+        match load_string(CONFIG_PATH).await {
+            Ok(contents) => Self::parse(&contents),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn parse(contents: &str) -> Self {
+        let default = Self::default();
+        let values = match TomlParser::parse(contents) {
+            Ok(values) => values,
+            Err(error) => {
+                warn!(
+                    "Could not parse {}, using defaults: {:?}",
+                    CONFIG_PATH, error
+                );
+                return default;
+            }
+        };
+
+        // Any value left out keeps its default
+        Self {
+            paw_acceleration: number(&values, "paw_acceleration", default.paw_acceleration),
+            paw_friction: number(&values, "paw_friction", default.paw_friction),
+            ball_shape: Vec2 {
+                x: number(&values, "ball_width", default.ball_shape.x),
+                y: number(&values, "ball_height", default.ball_shape.y),
+            },
+            base_ball_velocity: number(&values, "base_ball_velocity", default.base_ball_velocity),
+            game_shape: Vec2 {
+                x: number(&values, "game_width", default.game_shape.x),
+                y: number(&values, "game_height", default.game_shape.y),
+            },
+            touch_strategy: touch_strategy(&values, default.touch_strategy),
+            safety_net_combo: number(&values, "safety_net_combo", default.safety_net_combo as f32)
+                as u32,
+        }
+    }
+}
+
+fn number(values: &HashMap<String, Toml>, key: &str, default: f32) -> f32 {
+    match values.get(key) {
+        Some(Toml::Num(value)) => *value as f32,
+        Some(_) => {
+            warn!(
+                "{} in {} should be a number, using the default",
+                key, CONFIG_PATH
+            );
+            default
+        }
+        None => default,
+    }
+}
+
+fn touch_strategy(values: &HashMap<String, Toml>, default: TouchStrategy) -> TouchStrategy {
+    match values.get("touch_strategy") {
+        Some(Toml::Str(value)) => TouchStrategy::from_key(value).unwrap_or_else(|| {
+            warn!(
+                "touch_strategy in {} should be \"nearest\" or \"average\", using the default",
+                CONFIG_PATH
+            );
+            default
+        }),
+        Some(_) => {
+            warn!(
+                "touch_strategy in {} should be a string, using the default",
+                CONFIG_PATH
+            );
+            default
+        }
+        None => default,
+    }
+}
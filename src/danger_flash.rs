@@ -0,0 +1,70 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::config::Config;
+use crate::game_area::{Drawable, GameArea, TranslateType};
+use crate::layout::Layout;
+
+// A ball heading for the paws' edge within this fraction of the playfield's height of it is
+// about to be lost. The paws' faces are further out than this, so only a ball that's got past
+// them sets it off
+const DANGER_HEIGHT: f32 = 0.15;
+// The warning is a band this many game units deep along the edge, pulsing at this many radians
+// per second up to this alpha
+const DANGER_BAND_HEIGHT: f32 = 3.0;
+const DANGER_PULSE_SPEED: f32 = 18.0;
+const DANGER_MAX_ALPHA: f32 = 0.6;
+
+// A pulsing warning along the paws' edge of the playfield while a ball is close to falling off it,
+// gone as soon as the ball is bounced back
+pub struct DangerFlash {
+    game_shape: Vec2,
+    active: bool,
+    // Seconds the current warning has been showing
+    time: f32,
+}
+
+impl DangerFlash {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            game_shape: config.game_shape,
+            active: false,
+            time: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, balls: &[Ball], delta: f32) {
+        let danger_height = self.game_shape.y * DANGER_HEIGHT;
+        self.active = balls
+            .iter()
+            .any(|ball| ball.physics.in_danger(danger_height));
+        self.time = if self.active { self.time + delta } else { 0.0 };
+    }
+}
+
+impl Drawable for DangerFlash {
+    fn draw(&self, game_area: &GameArea) {
+        if !self.active {
+            return;
+        }
+        let y = match game_area.layout {
+            Layout::PawsAtBottom => self.game_shape.y - DANGER_BAND_HEIGHT,
+            Layout::PawsAtTop => 0.0,
+        };
+        let top_left = game_area.game_to_screen(Vec2 { x: 0.0, y }, TranslateType::Normal);
+        let size = game_area.game_to_screen(
+            Vec2 {
+                x: self.game_shape.x,
+                y: DANGER_BAND_HEIGHT,
+            },
+            TranslateType::JustScale,
+        );
+        // Starts at its brightest so the warning shows straight away
+        let pulse = 0.5 + 0.5 * (self.time * DANGER_PULSE_SPEED).cos();
+        let color = Color {
+            a: DANGER_MAX_ALPHA * pulse,
+            ..game_area.theme.danger()
+        };
+        draw_rectangle(top_left.x, top_left.y, size.x, size.y, color);
+    }
+}
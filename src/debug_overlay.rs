@@ -0,0 +1,121 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::fps_counter::FpsCounter;
+use crate::game_area::{GameArea, TranslateType};
+use crate::input_state::{screen_touches, steered_paw, steering_touch};
+use crate::pause_gesture::PauseGesture;
+use crate::paw::{Paw, PawSide};
+
+const DEBUG_FONT_SIZE: f32 = 20.0;
+const DEBUG_LINE_HEIGHT: f32 = 22.0;
+// Each touch gets a marker this many game units across, colored by the paw it steers or as
+// ignored
+const TOUCH_MARKER_RADIUS: f32 = 1.5;
+const LEFT_PAW_COLOR: Color = SKYBLUE;
+const RIGHT_PAW_COLOR: Color = ORANGE;
+const IGNORED_TOUCH_COLOR: Color = RED;
+
+// Diagnostics for tuning the physics and checking touches land on the right paw, toggled with F3
+pub struct DebugOverlay {
+    enabled: bool,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self { enabled: false }
+    }
+
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::F3) {
+            self.enabled = !self.enabled;
+        }
+    }
+
+    // Drawn in screen units in the window's corner so it stays out of the playfield when possible
+    pub fn draw(
+        &self,
+        game_area: &GameArea,
+        fps_counter: &FpsCounter,
+        balls: &[Ball],
+        left_paw: &Paw,
+        right_paw: &Paw,
+        pause_gesture: &PauseGesture,
+    ) {
+        if !self.enabled {
+            return;
+        }
+        self.draw_touches(game_area, left_paw.is_mirrored(), pause_gesture);
+        let mut lines = vec![
+            format!(
+                "FPS: {:.0} (this frame {:.0})",
+                fps_counter.smoothed_fps(),
+                fps_counter.fps()
+            ),
+            format!(
+                "Frame time: {:.2} ms (this frame {:.2} ms)",
+                fps_counter.smoothed_frame_time() * 1000.0,
+                get_frame_time() * 1000.0
+            ),
+        ];
+        lines.extend(balls.iter().map(|ball| {
+            format!(
+                "Ball velocity: {:.1}, {:.1}",
+                ball.physics.velocity.x, ball.physics.velocity.y
+            )
+        }));
+        lines.push(format!("Left paw velocity: {:.1}", left_paw.velocity.x));
+        lines.push(format!("Right paw velocity: {:.1}", right_paw.velocity.x));
+
+        let width = lines
+            .iter()
+            .map(|line| measure_text(line, None, DEBUG_FONT_SIZE as u16, 1.0).width)
+            .fold(0.0, f32::max);
+        draw_rectangle(
+            0.0,
+            0.0,
+            width + 10.0,
+            lines.len() as f32 * DEBUG_LINE_HEIGHT + 10.0,
+            Color::new(0.0, 0.0, 0.0, 0.7),
+        );
+        for (index, line) in lines.iter().enumerate() {
+            draw_text(
+                line,
+                5.0,
+                (index + 1) as f32 * DEBUG_LINE_HEIGHT,
+                DEBUG_FONT_SIZE,
+                WHITE,
+            );
+        }
+    }
+
+    // Where each touch lands after the same filtering the paws do, colored by the paw it steers
+    // so mirrored controls show up crossed over. Ones beside the field, on the mute or pause
+    // buttons or the hold spot, or right on the center line don't steer either paw
+    fn draw_touches(&self, game_area: &GameArea, mirrored: bool, pause_gesture: &PauseGesture) {
+        let radius = game_area
+            .game_to_screen(
+                Vec2 {
+                    x: TOUCH_MARKER_RADIUS,
+                    y: TOUCH_MARKER_RADIUS,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        for position in screen_touches() {
+            let touch = steering_touch(position, game_area, pause_gesture);
+            let steered = touch.and_then(|touch| steered_paw(touch, game_area.shape, mirrored));
+            let color = match steered {
+                Some(PawSide::Left) => LEFT_PAW_COLOR,
+                Some(PawSide::Right) => RIGHT_PAW_COLOR,
+                None => IGNORED_TOUCH_COLOR,
+            };
+            draw_circle_lines(position.x, position.y, radius, 2.0, color);
+            if let (Some(touch), Some(_)) = (touch, steered) {
+                // Where it counts as being, on the field's edge for a touch above or below it
+                let counted = game_area.game_to_screen(touch, TranslateType::Normal);
+                draw_circle(counted.x, counted.y, radius * 0.4, color);
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::lang::Text;
+use crate::storage::{load_value, save_value};
+
+const DIFFICULTY_KEY: &str = "difficulty";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    // The last chosen difficulty, or Normal if there isn't a saved one
+    pub fn load() -> Self {
+        let saved = load_value(DIFFICULTY_KEY);
+        Self::ALL
+            .into_iter()
+            .find(|difficulty| saved.as_deref() == Some(difficulty.key()))
+            .unwrap_or(Difficulty::Normal)
+    }
+
+    pub fn save(&self) {
+        save_value(DIFFICULTY_KEY, self.key());
+    }
+
+    // Name used for this difficulty in saved values
+    pub fn key(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn label(&self) -> Text {
+        match self {
+            Difficulty::Easy => Text::Easy,
+            Difficulty::Normal => Text::Normal,
+            Difficulty::Hard => Text::Hard,
+        }
+    }
+
+    // The difficulty after this one, wrapping back round to Easy
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+
+    // How quickly the ball speeds up as the score climbs
+    pub fn speed_growth(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.8,
+        }
+    }
+
+    // Scales how hard touches and keys push the paws
+    pub fn paw_acceleration_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.2,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.85,
+        }
+    }
+
+    // Hard puts obstacles in the middle of the playfield
+    pub fn has_obstacles(&self) -> bool {
+        *self == Difficulty::Hard
+    }
+
+    // Balls that can be lost before the game is over
+    pub fn lives(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 3,
+            Difficulty::Hard => 2,
+        }
+    }
+
+    // Scales the paws' width
+    pub fn paw_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.8,
+        }
+    }
+
+    // Scales the ball's size, a smaller ball is harder to hit
+    pub fn ball_scale(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.15,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.8,
+        }
+    }
+}
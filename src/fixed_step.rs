@@ -0,0 +1,36 @@
+// The physics is stepped this many seconds of game time at a time whatever the frame rate, so
+// a run plays out the same however fast it's drawn
+pub const FIXED_STEP: f32 = 1.0 / 60.0;
+// A long hitch only catches up this many steps, rather than freezing while it churns through
+// all of them
+const MAX_STEPS: u32 = 5;
+
+// Banks each frame's game time and pays it out in whole physics steps. What's left over says
+// how far the balls and paws are drawn between where the last step left them and the one before
+pub struct FixedStep {
+    // Game time not yet stepped through
+    accumulator: f32,
+}
+
+impl FixedStep {
+    pub fn new() -> Self {
+        Self { accumulator: 0.0 }
+    }
+
+    // How many steps to take for `delta` more seconds of game time
+    pub fn advance(&mut self, delta: f32) -> u32 {
+        self.accumulator = (self.accumulator + delta).min(FIXED_STEP * MAX_STEPS as f32);
+        let steps = (self.accumulator / FIXED_STEP) as u32;
+        self.accumulator -= steps as f32 * FIXED_STEP;
+        steps
+    }
+
+    // From 0 at the step before's positions to 1 at the last step's
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / FIXED_STEP).clamp(0.0, 1.0)
+    }
+
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+    }
+}
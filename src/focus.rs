@@ -0,0 +1,55 @@
+use macroquad::prelude::*;
+
+// A frame taking longer than this many seconds means the game wasn't running in between, like
+// when a window is dragged or hidden, or a browser tab comes back from the background already
+// focused again. Well above the odd slow frame from a GC pause or an asset load, which shouldn't
+// pause a run
+const MAX_FRAME_GAP: f32 = 1.0;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_has_focus() -> bool;
+}
+
+// Whether the player has looked away from the game since the last frame, as far as it can tell.
+// The web asks the page whether it has focus. Macroquad doesn't pass on focus changes on desktop,
+// so there it only catches the long gap in frames a hidden window leaves, a minimized one being
+// caught by the playfield losing its size
+pub fn focus_lost() -> bool {
+    looked_away(get_frame_time(), has_focus())
+}
+
+fn looked_away(frame_time: f32, has_focus: bool) -> bool {
+    !has_focus || frame_time > MAX_FRAME_GAP
+}
+
+#[cfg(target_arch = "wasm32")]
+fn has_focus() -> bool {
+    unsafe { cat_ball_wow_has_focus() }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn has_focus() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn losing_focus_is_looking_away() {
+        assert!(looked_away(0.016, false));
+        assert!(!looked_away(0.016, true));
+    }
+
+    #[test]
+    fn only_a_long_gap_in_frames_is_looking_away() {
+        // Slow frames, up to a stutter from a GC pause or loading an asset
+        for frame_time in [0.016, 0.1, 0.3, 0.5, MAX_FRAME_GAP] {
+            assert!(!looked_away(frame_time, true), "{frame_time}");
+        }
+        assert!(looked_away(MAX_FRAME_GAP + 0.1, true));
+        assert!(looked_away(10.0, true));
+    }
+}
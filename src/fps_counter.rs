@@ -0,0 +1,50 @@
+use macroquad::prelude::*;
+
+// How much of each new frame time goes into the average, lower is smoother but slower to follow
+const SMOOTHING: f32 = 0.05;
+
+// Frame rate averaged over recent frames, since the raw rate jumps around too much to read
+pub struct FpsCounter {
+    // Exponential moving average of the frame time in seconds
+    smoothed_frame_time: f32,
+}
+
+impl FpsCounter {
+    pub fn new() -> Self {
+        Self {
+            smoothed_frame_time: 0.0,
+        }
+    }
+
+    // Called once every frame
+    pub fn update(&mut self) {
+        let frame_time = get_frame_time();
+        // Starts from the first frame rather than easing up from zero
+        if self.smoothed_frame_time == 0.0 {
+            self.smoothed_frame_time = frame_time;
+        } else {
+            self.smoothed_frame_time += (frame_time - self.smoothed_frame_time) * SMOOTHING;
+        }
+    }
+
+    // The rate the last frame alone ran at
+    pub fn fps(&self) -> f32 {
+        fps_from(get_frame_time())
+    }
+
+    pub fn smoothed_fps(&self) -> f32 {
+        fps_from(self.smoothed_frame_time)
+    }
+
+    pub fn smoothed_frame_time(&self) -> f32 {
+        self.smoothed_frame_time
+    }
+}
+
+fn fps_from(frame_time: f32) -> f32 {
+    if frame_time > 0.0 {
+        1.0 / frame_time
+    } else {
+        0.0
+    }
+}
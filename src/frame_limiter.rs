@@ -0,0 +1,54 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+use crate::storage::{load_value, save_value};
+
+// Frame rate the loop is held to while the cap is on
+pub const FPS_CAP: u32 = 60;
+const FPS_CAP_KEY: &str = "fps_cap";
+
+// Keeps the loop from spinning faster than FPS_CAP and burning battery. The browser already
+// paces frames on the web, so there it does nothing
+pub struct FrameLimiter {
+    enabled: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    last_frame: Instant,
+}
+
+impl FrameLimiter {
+    // Whether capping does anything on this platform
+    pub const SUPPORTED: bool = cfg!(not(target_arch = "wasm32"));
+
+    pub fn new() -> Self {
+        Self {
+            // On unless it's been saved as off
+            enabled: load_value(FPS_CAP_KEY).as_deref() != Some("false"),
+            #[cfg(not(target_arch = "wasm32"))]
+            last_frame: Instant::now(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(FPS_CAP_KEY, &self.enabled.to_string());
+    }
+
+    // Sleeps out whatever is left of this frame's share of a second, call just before next_frame
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn wait(&mut self) {
+        if self.enabled {
+            let frame_time = Duration::from_secs_f64(1.0 / FPS_CAP as f64);
+            if let Some(remaining) = frame_time.checked_sub(self.last_frame.elapsed()) {
+                std::thread::sleep(remaining);
+            }
+        }
+        self.last_frame = Instant::now();
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn wait(&mut self) {}
+}
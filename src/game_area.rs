@@ -0,0 +1,436 @@
+use macroquad::prelude::*;
+
+use crate::config::Config;
+use crate::lang::Lang;
+use crate::layout::Layout;
+use crate::theme::Theme;
+
+// The dashed line down the middle showing where each paw's half ends, in game units
+const CENTER_LINE_DASH: f32 = 3.0;
+const CENTER_LINE_GAP: f32 = 2.0;
+const CENTER_LINE_THICKNESS: f32 = 0.6;
+// The walls the ball bounces off are drawn as bands this many game units thick along the inside
+// of the three edges away from the paws
+const WALL_THICKNESS: f32 = 1.2;
+
+pub trait Drawable {
+    fn draw(&self, game_area: &GameArea);
+}
+
+// A textured object that is drawn filling its rect in game units
+pub trait Sprite {
+    fn rect(&self) -> Rect;
+    fn texture(&self) -> &Texture2D;
+
+    // Rotation in radians around the sprite's center, which doesn't affect its rect
+    fn rotation(&self) -> f32 {
+        0.0
+    }
+
+    // Multiplied with the texture's colors
+    fn tint(&self, _theme: Theme) -> Color {
+        WHITE
+    }
+
+    fn draw(&self, game_area: &GameArea) {
+        self.draw_at(game_area, self.rect(), self.tint(game_area.theme));
+    }
+
+    // Draws the sprite's texture over `rect` instead of its own, for things like trails
+    fn draw_at(&self, game_area: &GameArea, rect: Rect, color: Color) {
+        let screen_size = game_area.game_to_screen(rect.size(), TranslateType::JustScale);
+        let screen_position = game_area.game_to_screen(rect.point(), TranslateType::Normal);
+
+        draw_texture_ex(
+            self.texture(),
+            screen_position.x,
+            screen_position.y,
+            color,
+            DrawTextureParams {
+                dest_size: Some(Vec2 {
+                    x: screen_size.x,
+                    y: screen_size.y,
+                }),
+                rotation: self.rotation(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+// The largest rect with the playfield's aspect ratio in the middle of a window `screen_size` in
+// size. A minimized window can be left with no size at all, which would squash the rect to
+// nothing and turn every screen_to_game into a divide by zero, so then there isn't one
+fn fit_rect(screen_size: Vec2, shape: Vec2) -> Option<Rect> {
+    if screen_size.x <= 0.0 || screen_size.y <= 0.0 {
+        return None;
+    }
+    let scale = (screen_size / shape).min_element();
+    let size = shape * scale;
+    Some(Rect {
+        x: screen_size.x / 2.0 - size.x / 2.0,
+        y: screen_size.y / 2.0 - size.y / 2.0,
+        w: size.x,
+        h: size.y,
+    })
+}
+
+pub enum TranslateType {
+    Normal,
+    JustScale,
+}
+
+pub struct GameArea {
+    pub rect: Rect,
+    // Size of the playfield in game units
+    pub shape: Vec2,
+    // Colors for everything drawn in the playfield
+    pub theme: Theme,
+    // Language for all the text drawn over it
+    pub lang: Lang,
+    // Which edge the paws are along
+    pub layout: Layout,
+    // How intense the field looks, from calm at 0 to fully heated up at 1
+    pub heat: f32,
+    texture: Texture2D,
+}
+
+impl GameArea {
+    pub fn new(texture: Texture2D, config: &Config) -> Self {
+        Self {
+            rect: Rect::default(),
+            shape: config.game_shape,
+            theme: Theme::load(),
+            lang: Lang::load(),
+            layout: Layout::load(),
+            heat: 0.0,
+            texture,
+        }
+    }
+
+    // Fits the playfield to the window, keeping the last rect and returning false while the
+    // window has no size at all
+    pub fn update(&mut self) -> bool {
+        let screen_size = Vec2 {
+            x: screen_width(),
+            y: screen_height(),
+        };
+        let Some(rect) = fit_rect(screen_size, self.shape) else {
+            return false;
+        };
+        self.rect = rect;
+        true
+    }
+
+    pub fn draw(&self) {
+        draw_texture_ex(
+            &self.texture,
+            self.rect.x,
+            self.rect.y,
+            Color::from_vec(
+                self.theme
+                    .field_tint()
+                    .to_vec()
+                    .lerp(self.theme.heat_tint().to_vec(), self.heat),
+            ),
+            DrawTextureParams {
+                dest_size: Some(Vec2 {
+                    x: self.rect.w,
+                    y: self.rect.h,
+                }),
+                ..Default::default()
+            },
+        );
+
+        let thickness = self
+            .game_to_screen(
+                Vec2 {
+                    x: CENTER_LINE_THICKNESS,
+                    y: CENTER_LINE_THICKNESS,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        let mut dash_start = 0.0;
+        while dash_start < self.shape.y {
+            let dash_end = (dash_start + CENTER_LINE_DASH).min(self.shape.y);
+            let start = self.game_to_screen(
+                Vec2 {
+                    x: self.shape.x / 2.0,
+                    y: dash_start,
+                },
+                TranslateType::Normal,
+            );
+            let end = self.game_to_screen(
+                Vec2 {
+                    x: self.shape.x / 2.0,
+                    y: dash_end,
+                },
+                TranslateType::Normal,
+            );
+            draw_line(
+                start.x,
+                start.y,
+                end.x,
+                end.y,
+                thickness,
+                self.theme.center_line(),
+            );
+            dash_start += CENTER_LINE_DASH + CENTER_LINE_GAP;
+        }
+
+        self.draw_walls();
+    }
+
+    // The paws' edge is left open, as that's the one the ball falls out of
+    fn draw_walls(&self) {
+        let far_wall_y = match self.layout {
+            Layout::PawsAtBottom => 0.0,
+            Layout::PawsAtTop => self.shape.y - WALL_THICKNESS,
+        };
+        let walls = [
+            Rect::new(0.0, 0.0, WALL_THICKNESS, self.shape.y),
+            Rect::new(
+                self.shape.x - WALL_THICKNESS,
+                0.0,
+                WALL_THICKNESS,
+                self.shape.y,
+            ),
+            Rect::new(0.0, far_wall_y, self.shape.x, WALL_THICKNESS),
+        ];
+        for wall in walls {
+            let top_left = self.game_to_screen(wall.point(), TranslateType::Normal);
+            let size = self.game_to_screen(wall.size(), TranslateType::JustScale);
+            draw_rectangle(top_left.x, top_left.y, size.x, size.y, self.theme.wall());
+        }
+    }
+
+    // Covers the parts of the window outside the playfield, on whichever axis has space left over
+    pub fn draw_letterbox(&self, color: Color) {
+        let screen_size = Vec2 {
+            x: screen_width(),
+            y: screen_height(),
+        };
+        // Left and right
+        draw_rectangle(0.0, 0.0, self.rect.x, screen_size.y, color);
+        draw_rectangle(
+            self.rect.right(),
+            0.0,
+            screen_size.x - self.rect.right(),
+            screen_size.y,
+            color,
+        );
+        // Top and bottom
+        draw_rectangle(0.0, 0.0, screen_size.x, self.rect.y, color);
+        draw_rectangle(
+            0.0,
+            self.rect.bottom(),
+            screen_size.x,
+            screen_size.y - self.rect.bottom(),
+            color,
+        );
+    }
+
+    pub fn game_to_screen(&self, game_point: Vec2, translate_type: TranslateType) -> Vec2 {
+        let current_shape = Vec2 {
+            x: self.rect.w,
+            y: self.rect.h,
+        };
+        let offset = Vec2 {
+            x: self.rect.x,
+            y: self.rect.y,
+        };
+        match translate_type {
+            TranslateType::Normal => ((current_shape / self.shape) * game_point) + offset,
+            TranslateType::JustScale => (current_shape / self.shape) * game_point,
+        }
+    }
+
+    pub fn screen_to_game(&self, screen_point: Vec2) -> Vec2 {
+        let current_shape = Vec2 {
+            x: self.rect.w,
+            y: self.rect.h,
+        };
+        let offset = Vec2 {
+            x: self.rect.x,
+            y: self.rect.y,
+        };
+        ((screen_point - offset) / current_shape) * self.shape
+    }
+
+    // Whether a point in screen units is over the playfield, edges included
+    pub fn contains_screen_point(&self, screen_point: Vec2) -> bool {
+        screen_point.x >= self.rect.x
+            && screen_point.x <= self.rect.right()
+            && screen_point.y >= self.rect.y
+            && screen_point.y <= self.rect.bottom()
+    }
+
+    // The nearest point inside the playfield, in game units
+    pub fn clamp_to_field(&self, game_point: Vec2) -> Vec2 {
+        game_point.clamp(Vec2::ZERO, self.shape)
+    }
+
+    pub fn draw_centered_text(&self, text: &str, game_y: f32, game_font_size: f32, color: Color) {
+        let font_size = self
+            .game_to_screen(
+                Vec2 {
+                    x: game_font_size,
+                    y: game_font_size,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        let text_dimensions = measure_text(text, None, font_size as u16, 1.0);
+        let text_position = self.game_to_screen(
+            Vec2 {
+                x: self.shape.x / 2.0,
+                y: game_y,
+            },
+            TranslateType::Normal,
+        );
+        draw_text(
+            text,
+            text_position.x - text_dimensions.width / 2.0,
+            text_position.y,
+            font_size,
+            color,
+        );
+    }
+
+    // An outlined box with the text centered in it, all in game units
+    pub fn draw_button(&self, text: &str, button: Rect, game_font_size: f32, color: Color) {
+        let top_left = self.game_to_screen(button.point(), TranslateType::Normal);
+        let size = self.game_to_screen(button.size(), TranslateType::JustScale);
+        let font_size = self
+            .game_to_screen(
+                Vec2 {
+                    x: game_font_size,
+                    y: game_font_size,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        draw_rectangle_lines(
+            top_left.x,
+            top_left.y,
+            size.x,
+            size.y,
+            (font_size * 0.12).max(1.0),
+            color,
+        );
+        let text_dimensions = measure_text(text, None, font_size as u16, 1.0);
+        draw_text(
+            text,
+            top_left.x + (size.x - text_dimensions.width) / 2.0,
+            top_left.y + (size.y + text_dimensions.offset_y) / 2.0,
+            font_size,
+            color,
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const SHAPE: Vec2 = Vec2 { x: 100.0, y: 100.0 };
+
+    // A playfield `shape` in size filling `rect` on screen, with nothing loaded from settings.
+    // Never drawn, so any texture id will do and nothing needs a window
+    pub fn game_area(shape: Vec2, rect: Rect) -> GameArea {
+        GameArea {
+            rect,
+            shape,
+            theme: Theme::Default,
+            lang: Lang::English,
+            layout: Layout::PawsAtBottom,
+            heat: 0.0,
+            texture: Texture2D::from_miniquad_texture(miniquad::TextureId::from_raw_id(
+                miniquad::RawId::OpenGl(0),
+            )),
+        }
+    }
+
+    #[test]
+    fn no_rect_fits_a_window_with_no_size() {
+        assert_eq!(fit_rect(Vec2::new(0.0, 700.0), SHAPE), None);
+        assert_eq!(fit_rect(Vec2::new(700.0, 0.0), SHAPE), None);
+        assert_eq!(fit_rect(Vec2::ZERO, SHAPE), None);
+        assert_eq!(fit_rect(Vec2::new(-1.0, 700.0), SHAPE), None);
+    }
+
+    #[test]
+    fn playfield_is_fitted_in_the_middle_of_the_window() {
+        assert_eq!(
+            fit_rect(Vec2::new(700.0, 700.0), SHAPE),
+            Some(Rect::new(0.0, 0.0, 700.0, 700.0))
+        );
+        // Bars either side of a wide window, and above and below a tall one
+        assert_eq!(
+            fit_rect(Vec2::new(1000.0, 600.0), SHAPE),
+            Some(Rect::new(200.0, 0.0, 600.0, 600.0))
+        );
+        assert_eq!(
+            fit_rect(Vec2::new(400.0, 800.0), SHAPE),
+            Some(Rect::new(0.0, 200.0, 400.0, 400.0))
+        );
+    }
+
+    #[test]
+    fn screen_points_on_the_playfields_edges_are_over_it() {
+        let game_area = game_area(SHAPE, Rect::new(200.0, 100.0, 400.0, 400.0));
+        for point in [
+            Vec2::new(200.0, 300.0),
+            Vec2::new(600.0, 300.0),
+            Vec2::new(400.0, 100.0),
+            Vec2::new(400.0, 500.0),
+            Vec2::new(200.0, 100.0),
+            Vec2::new(600.0, 500.0),
+        ] {
+            assert!(game_area.contains_screen_point(point), "{point}");
+        }
+    }
+
+    #[test]
+    fn screen_points_just_outside_the_playfield_are_not_over_it() {
+        let game_area = game_area(SHAPE, Rect::new(200.0, 100.0, 400.0, 400.0));
+        for point in [
+            Vec2::new(199.9, 300.0),
+            Vec2::new(600.1, 300.0),
+            Vec2::new(400.0, 99.9),
+            Vec2::new(400.0, 500.1),
+        ] {
+            assert!(!game_area.contains_screen_point(point), "{point}");
+        }
+    }
+
+    #[test]
+    fn points_past_each_edge_clamp_onto_it() {
+        let game_area = game_area(Vec2::new(177.8, 100.0), Rect::default());
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(-5.0, 50.0)),
+            Vec2::new(0.0, 50.0)
+        );
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(200.0, 50.0)),
+            Vec2::new(177.8, 50.0)
+        );
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(30.0, -20.0)),
+            Vec2::new(30.0, 0.0)
+        );
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(30.0, 130.0)),
+            Vec2::new(30.0, 100.0)
+        );
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(-1.0, 101.0)),
+            Vec2::new(0.0, 100.0)
+        );
+        assert_eq!(
+            game_area.clamp_to_field(Vec2::new(30.0, 50.0)),
+            Vec2::new(30.0, 50.0)
+        );
+    }
+}
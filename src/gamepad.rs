@@ -0,0 +1,127 @@
+#[cfg(not(target_arch = "wasm32"))]
+use gilrs::{Axis, Button, EventType, Gilrs};
+#[cfg(not(target_arch = "wasm32"))]
+use macroquad::prelude::*;
+
+use crate::paw::PawSide;
+
+// Stick or trigger movement smaller than this is treated as resting, so a worn one doesn't drift
+// the paw
+#[cfg(not(target_arch = "wasm32"))]
+const STICK_DEAD_ZONE: f32 = 0.15;
+
+// Gamepads on desktop through gilrs, macroquad itself has no gamepad input. Browsers aren't
+// supported, there every gamepad reads as resting
+pub struct Gamepads {
+    #[cfg(not(target_arch = "wasm32"))]
+    gilrs: Option<Gilrs>,
+    // Buttons pressed since the last update
+    #[cfg(not(target_arch = "wasm32"))]
+    pressed: Vec<Button>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Gamepads {
+    pub fn new() -> Self {
+        // No gamepad support just means playing without one
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(error) => {
+                warn!("Gamepads are unavailable: {}", error);
+                None
+            }
+        };
+        Self {
+            gilrs,
+            pressed: Vec::new(),
+        }
+    }
+
+    // Call once a frame, before reading any input
+    pub fn update(&mut self) {
+        self.pressed.clear();
+        let Some(gilrs) = self.gilrs.as_mut() else {
+            return;
+        };
+        while let Some(event) = gilrs.next_event() {
+            if let EventType::ButtonPressed(button, _) = event.event {
+                self.pressed.push(button);
+            }
+        }
+    }
+
+    // How far the paw is pushed, from -1 (left) to 1 (right), across all gamepads. The left stick
+    // drives the left paw and the right stick the right paw. Each side's trigger is an
+    // alternative, pushing its paw out towards its wall as far as it's squeezed, with the bumper
+    // above it pushing it back in
+    pub fn paw_input(&self, paw_side: PawSide) -> f32 {
+        let Some(gilrs) = self.gilrs.as_ref() else {
+            return 0.0;
+        };
+        let (stick, trigger_axis, trigger, bumper, outwards) = match paw_side {
+            PawSide::Left => (
+                Axis::LeftStickX,
+                Axis::LeftZ,
+                Button::LeftTrigger2,
+                Button::LeftTrigger,
+                -1.0,
+            ),
+            PawSide::Right => (
+                Axis::RightStickX,
+                Axis::RightZ,
+                Button::RightTrigger2,
+                Button::RightTrigger,
+                1.0,
+            ),
+        };
+        gilrs
+            .gamepads()
+            .map(|(_, gamepad)| {
+                // Some drivers report the analog triggers as buttons and some as axes
+                let squeezed = gamepad
+                    .button_data(trigger)
+                    .map_or(0.0, |data| data.value())
+                    .max(gamepad.value(trigger_axis));
+                let bumped = if gamepad.is_pressed(bumper) { 1.0 } else { 0.0 };
+                [gamepad.value(stick), (squeezed - bumped) * outwards]
+                    .into_iter()
+                    .filter(|value| value.abs() > STICK_DEAD_ZONE)
+                    .sum::<f32>()
+            })
+            .sum::<f32>()
+            .clamp(-1.0, 1.0)
+    }
+
+    // A or Start, to start, resume and retry like Space does
+    pub fn confirm_pressed(&self) -> bool {
+        self.pressed
+            .iter()
+            .any(|button| matches!(button, Button::South | Button::Start))
+    }
+
+    // Start, to pause like Escape does
+    pub fn pause_pressed(&self) -> bool {
+        self.pressed.contains(&Button::Start)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Gamepads {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn update(&mut self) {}
+
+    pub fn paw_input(&self, _paw_side: PawSide) -> f32 {
+        0.0
+    }
+
+    pub fn confirm_pressed(&self) -> bool {
+        false
+    }
+
+    pub fn pause_pressed(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,65 @@
+use macroquad::prelude::*;
+
+use crate::storage::{load_value, save_value};
+
+// A new vibration won't start within this many seconds of the last, so fast rallies don't buzz
+// continuously
+const VIBRATION_COOLDOWN: f64 = 0.15;
+const HAPTICS_KEY: &str = "haptics";
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_vibrate(milliseconds: u32);
+}
+
+// Short vibrations for collisions, through navigator.vibrate on the web and doing nothing elsewhere
+pub struct Haptics {
+    enabled: bool,
+    last_vibration: f64,
+}
+
+impl Haptics {
+    // Whether vibrating does anything on this platform
+    pub const SUPPORTED: bool = cfg!(target_arch = "wasm32");
+
+    pub fn new() -> Self {
+        Self {
+            // On unless it's been saved as off
+            enabled: load_value(HAPTICS_KEY).as_deref() != Some("false"),
+            last_vibration: f64::NEG_INFINITY,
+        }
+    }
+
+    // Never vibrates whatever the setting, for games going on in the background
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            last_vibration: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(HAPTICS_KEY, &self.enabled.to_string());
+    }
+
+    pub fn vibrate(&mut self, milliseconds: u32) {
+        if !self.enabled || get_time() - self.last_vibration < VIBRATION_COOLDOWN {
+            return;
+        }
+        self.last_vibration = get_time();
+        vibrate(milliseconds);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn vibrate(milliseconds: u32) {
+    unsafe { cat_ball_wow_vibrate(milliseconds) };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn vibrate(_milliseconds: u32) {}
@@ -0,0 +1,199 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+use nanoserde::{DeJson, SerJson};
+
+use crate::date::format_date;
+use crate::difficulty::Difficulty;
+use crate::game_area::GameArea;
+use crate::lang::Text;
+use crate::scores::{format_duration, ScoreMode};
+use crate::storage::{load_document, save_document};
+
+const HIGH_SCORES_NAME: &str = "high_scores.json";
+// Each mode keeps its own table of this many
+const MAX_HIGH_SCORES: usize = 10;
+// Rows of the table in game units
+const TABLE_TOP: f32 = 33.0;
+const ROW_HEIGHT: f32 = 6.5;
+
+#[derive(SerJson, DeJson)]
+struct HighScore {
+    // Bounces, or whole seconds survived for survival
+    score: u32,
+    // The difficulty's saved key, kept as text so old entries survive difficulties changing
+    difficulty: String,
+    // The mode's saved key. Entries from before modes had tables of their own were all bounces
+    #[nserde(default = "bounces")]
+    mode: String,
+    // Seconds since the Unix epoch
+    timestamp: u64,
+}
+
+impl HighScore {
+    fn result(&self) -> String {
+        if self.mode == ScoreMode::Survival.key() {
+            format_duration(self.score as f32)
+        } else {
+            self.score.to_string()
+        }
+    }
+}
+
+// The best runs of all time in each mode, best first
+pub struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    pub fn new() -> Self {
+        // A missing or corrupt file just means an empty table
+        let entries = load_document(HIGH_SCORES_NAME)
+            .and_then(|contents| Vec::<HighScore>::deserialize_json(&contents).ok())
+            .unwrap_or_default();
+        let mut high_scores = Self { entries };
+        high_scores.sort();
+        high_scores
+    }
+
+    // Adds a finished run, if it's good enough to make its mode's table. `score` is the bounces,
+    // or the seconds survived for survival
+    pub fn insert(&mut self, score: u32, difficulty: Difficulty, mode: ScoreMode) {
+        if score == 0 {
+            return;
+        }
+        self.entries.push(HighScore {
+            score,
+            difficulty: difficulty.key().to_string(),
+            mode: mode.key().to_string(),
+            timestamp: miniquad::date::now() as u64,
+        });
+        self.sort();
+        save_document(HIGH_SCORES_NAME, &self.entries.serialize_json());
+    }
+
+    fn sort(&mut self) {
+        self.entries
+            .sort_by_key(|entry| std::cmp::Reverse(entry.score));
+        let mut counts = HashMap::<String, usize>::new();
+        self.entries.retain(|entry| {
+            let count = counts.entry(entry.mode.clone()).or_default();
+            *count += 1;
+            *count <= MAX_HIGH_SCORES
+        });
+    }
+
+    fn entries(&self, mode: ScoreMode) -> impl Iterator<Item = &HighScore> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.mode == mode.key())
+    }
+
+    // The table for the mode being played
+    pub fn draw(&self, game_area: &GameArea, mode: ScoreMode) {
+        // Fade out the playfield like the game over screen does
+        draw_rectangle(
+            game_area.rect.x,
+            game_area.rect.y,
+            game_area.rect.w,
+            game_area.rect.h,
+            game_area.theme.overlay(0.8),
+        );
+        let lang = game_area.lang;
+        game_area.draw_centered_text(
+            lang.get(Text::HighScores),
+            20.0,
+            12.0,
+            game_area.theme.text(),
+        );
+        game_area.draw_centered_text(lang.get(mode.label()), 27.0, 5.0, game_area.theme.text());
+
+        if self.entries(mode).next().is_none() {
+            game_area.draw_centered_text(
+                lang.get(Text::NoScoresYet),
+                50.0,
+                6.0,
+                game_area.theme.text(),
+            );
+        }
+        for (index, entry) in self.entries(mode).enumerate() {
+            let difficulty = Difficulty::ALL
+                .into_iter()
+                .find(|difficulty| difficulty.key() == entry.difficulty)
+                .map_or("?", |difficulty| lang.get(difficulty.label()));
+            game_area.draw_centered_text(
+                &format!(
+                    "{}. {}  {}  {}",
+                    index + 1,
+                    entry.result(),
+                    difficulty,
+                    format_date(entry.timestamp)
+                ),
+                TABLE_TOP + index as f32 * ROW_HEIGHT,
+                5.0,
+                game_area.theme.text(),
+            );
+        }
+
+        game_area.draw_centered_text(
+            lang.get(Text::TapToGoBack),
+            96.0,
+            6.0,
+            game_area.theme.text(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u32, mode: ScoreMode) -> HighScore {
+        HighScore {
+            score,
+            difficulty: Difficulty::Normal.key().to_string(),
+            mode: mode.key().to_string(),
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn survival_entries_are_times() {
+        assert_eq!(entry(75, ScoreMode::Survival).result(), "01:15");
+        assert_eq!(entry(75, ScoreMode::Bounces).result(), "75");
+    }
+
+    #[test]
+    fn entries_from_before_modes_are_bounces() {
+        let entries = Vec::<HighScore>::deserialize_json(
+            r#"[{"score":12,"difficulty":"normal","timestamp":0}]"#,
+        )
+        .unwrap();
+        assert_eq!(entries[0].mode, ScoreMode::Bounces.key());
+    }
+
+    #[test]
+    fn each_mode_keeps_its_own_best_runs() {
+        // Long survival times mustn't push every bounces run out of the table
+        let mut high_scores = HighScores {
+            entries: (0..15)
+                .map(|score| entry(1000 + score, ScoreMode::Survival))
+                .chain((0..15).map(|score| entry(score, ScoreMode::Bounces)))
+                .collect(),
+        };
+        high_scores.sort();
+        for mode in [ScoreMode::Survival, ScoreMode::Bounces] {
+            let scores: Vec<u32> = high_scores.entries(mode).map(|entry| entry.score).collect();
+            assert_eq!(scores.len(), MAX_HIGH_SCORES);
+            assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        }
+        assert_eq!(
+            high_scores
+                .entries(ScoreMode::Bounces)
+                .next()
+                .unwrap()
+                .score,
+            14
+        );
+    }
+}
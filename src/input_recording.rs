@@ -0,0 +1,233 @@
+use macroquad::prelude::*;
+
+use crate::input_state::InputState;
+use crate::storage::{load_document, save_document};
+
+const RECORDING_NAME: &str = "input_recording.txt";
+const INDICATOR_FONT_SIZE: f32 = 20.0;
+
+#[derive(PartialEq)]
+enum Mode {
+    Off,
+    Recording,
+    Playback,
+}
+
+// Records every frame's input through a run, so a bug report can come with exactly what led up
+// to it, and plays a recorded run back through the game frame by frame. F9 on the menu starts or
+// stops recording the runs played, F10 plays the last recorded one back
+pub struct InputRecording {
+    mode: Mode,
+    // Whether the run being recorded or played back has started
+    running: bool,
+    // Seed the run's random numbers started from, and the settings it was played with
+    seed: u64,
+    settings: String,
+    frames: Vec<InputState>,
+    // Frames played back so far, and the score the recorded run ended with
+    position: usize,
+    recorded_score: u32,
+}
+
+impl InputRecording {
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Off,
+            running: false,
+            seed: 0,
+            settings: String::new(),
+            frames: Vec::new(),
+            position: 0,
+            recorded_score: 0,
+        }
+    }
+
+    // Only checked for on the menu, so a run is always recorded or played back from its start
+    pub fn update(&mut self) {
+        if is_key_pressed(KeyCode::F9) {
+            self.mode = match self.mode {
+                Mode::Recording => Mode::Off,
+                Mode::Off | Mode::Playback => Mode::Recording,
+            };
+        }
+        if is_key_pressed(KeyCode::F10) {
+            if self.load() {
+                self.mode = Mode::Playback;
+            } else {
+                warn!("No input recording to play back");
+            }
+        }
+    }
+
+    // On the first frame of a run that's recorded or played back, gives the seed the game's
+    // random numbers should start the run from
+    pub fn start_run(&mut self, settings: &str) -> Option<u64> {
+        if self.mode == Mode::Off || self.running {
+            return None;
+        }
+        self.running = true;
+        match self.mode {
+            Mode::Recording => {
+                self.seed = miniquad::date::now() as u64;
+                self.settings = settings.to_string();
+                self.frames.clear();
+            }
+            Mode::Playback => {
+                // Still played back, but the run will likely go differently
+                if settings != self.settings {
+                    warn!(
+                        "Playing back a run recorded with different settings: {} (now {})",
+                        self.settings, settings
+                    );
+                }
+                self.position = 0;
+            }
+            Mode::Off => {}
+        }
+        Some(self.seed)
+    }
+
+    // This frame's input, played back in place of the player's own during playback
+    pub fn input(&mut self, live: InputState) -> InputState {
+        match self.mode {
+            Mode::Recording if self.running => {
+                self.frames.push(live.clone());
+                live
+            }
+            Mode::Playback if self.running => {
+                self.position += 1;
+                // Once the recording runs out nothing more is touched or pressed
+                self.frames
+                    .get(self.position - 1)
+                    .cloned()
+                    .unwrap_or(InputState {
+                        delta: live.delta,
+                        frame_time: live.frame_time,
+                        ..Default::default()
+                    })
+            }
+            _ => live,
+        }
+    }
+
+    // Saves a recorded run, or checks a played back one ended the same way it did when recorded
+    pub fn end_run(&mut self, score: u32) {
+        if !self.running {
+            return;
+        }
+        self.running = false;
+        match self.mode {
+            Mode::Recording => {
+                self.recorded_score = score;
+                self.save();
+            }
+            Mode::Playback => {
+                if score == self.recorded_score {
+                    info!("Played back the recorded run to the same score, {}", score);
+                } else {
+                    warn!(
+                        "Played back the recorded run to a score of {}, it was recorded with {}",
+                        score, self.recorded_score
+                    );
+                }
+                self.mode = Mode::Off;
+            }
+            Mode::Off => {}
+        }
+    }
+
+    // Drawn in screen units in the window's corner, like the debug overlay
+    pub fn draw(&self) {
+        let text = match self.mode {
+            Mode::Off => return,
+            Mode::Recording => "REC",
+            Mode::Playback => "PLAYBACK",
+        };
+        let width = measure_text(text, None, INDICATOR_FONT_SIZE as u16, 1.0).width;
+        draw_text(
+            text,
+            screen_width() - width - 5.0,
+            INDICATOR_FONT_SIZE,
+            INDICATOR_FONT_SIZE,
+            RED,
+        );
+    }
+
+    // Saved as the seed and score on the first line, the settings on the second, then a line per
+    // frame of its time steps, steering, tilt (or -) and touches as x,y pairs
+    fn save(&self) {
+        let mut contents = format!("{} {}\n{}\n", self.seed, self.recorded_score, self.settings);
+        for frame in &self.frames {
+            let tilt = frame.tilt.map_or("-".to_string(), |tilt| tilt.to_string());
+            let touches: Vec<String> = frame
+                .touches
+                .iter()
+                .map(|touch| format!("{},{}", touch.x, touch.y))
+                .collect();
+            contents.push_str(&format!(
+                "{} {} {} {} {} {}\n",
+                frame.delta,
+                frame.frame_time,
+                frame.left_steering,
+                frame.right_steering,
+                tilt,
+                touches.join(" ")
+            ));
+        }
+        save_document(RECORDING_NAME, &contents);
+    }
+
+    // A corrupt line means a corrupt recording, which would play back a different run anyway, so
+    // it's turned down altogether
+    fn load(&mut self) -> bool {
+        let Some(contents) = load_document(RECORDING_NAME) else {
+            return false;
+        };
+        let mut lines = contents.lines();
+        let Some((seed, recorded_score)) = lines.next().and_then(|line| {
+            let (seed, score) = line.split_once(' ')?;
+            Some((seed.parse().ok()?, score.parse().ok()?))
+        }) else {
+            return false;
+        };
+        let settings = lines.next().unwrap_or_default().to_string();
+        let Some(frames) = lines.map(parse_frame).collect::<Option<Vec<_>>>() else {
+            return false;
+        };
+        self.seed = seed;
+        self.recorded_score = recorded_score;
+        self.settings = settings;
+        self.frames = frames;
+        true
+    }
+}
+
+fn parse_frame(line: &str) -> Option<InputState> {
+    let mut fields = line.split_whitespace();
+    let mut numbers = [0.0; 4];
+    for number in numbers.iter_mut() {
+        *number = fields.next()?.parse().ok()?;
+    }
+    let [delta, frame_time, left_steering, right_steering] = numbers;
+    let tilt = match fields.next()? {
+        "-" => None,
+        tilt => Some(tilt.parse().ok()?),
+    };
+    let touches = fields
+        .map(|touch| {
+            let (x, y) = touch.split_once(',')?;
+            Some(Vec2 {
+                x: x.parse().ok()?,
+                y: y.parse().ok()?,
+            })
+        })
+        .collect::<Option<_>>()?;
+    Some(InputState {
+        touches,
+        left_steering,
+        right_steering,
+        tilt,
+        delta,
+        frame_time,
+    })
+}
@@ -0,0 +1,263 @@
+use macroquad::prelude::*;
+
+use crate::game_area::GameArea;
+use crate::gamepad::Gamepads;
+use crate::pause_button::PAUSE_BUTTON;
+use crate::pause_gesture::PauseGesture;
+use crate::paw::PawSide;
+use crate::slow_motion::SlowMotion;
+use crate::sounds::MUTE_BUTTON;
+use crate::tilt::Tilt;
+
+// Everything from the player and the clock that the game moves by each frame, gathered in one
+// place so the game can also be stepped with made up or recorded input instead of macroquad's
+#[derive(Clone, Default)]
+pub struct InputState {
+    // Touch positions in game units
+    pub touches: Vec<Vec2>,
+    // Each paw's keys and gamepad stick together, 1 or -1 for a key held towards a side
+    pub left_steering: f32,
+    pub right_steering: f32,
+    // Steering from tilting the device, if tilt steering is on and has a reading
+    pub tilt: Option<f32>,
+    // Seconds of game time to step by
+    pub delta: f32,
+    // Real seconds since the last frame, for the timers that ignore slow motion
+    pub frame_time: f32,
+}
+
+impl InputState {
+    // This frame's touches, keys, sticks and tilt, and its frame time slowed by any slow motion
+    pub fn from_frame(
+        game_area: &GameArea,
+        slow_motion: &SlowMotion,
+        gamepads: &Gamepads,
+        tilt: &Tilt,
+        pause_gesture: &PauseGesture,
+    ) -> Self {
+        let touches = screen_touches()
+            .into_iter()
+            .filter_map(|position| steering_touch(position, game_area, pause_gesture))
+            .collect();
+
+        Self {
+            touches,
+            left_steering: steering(PawSide::Left, gamepads),
+            right_steering: steering(PawSide::Right, gamepads),
+            tilt: tilt.steering(),
+            delta: slow_motion.delta(),
+            frame_time: get_frame_time(),
+        }
+    }
+
+    pub fn steering(&self, paw_side: PawSide) -> f32 {
+        match paw_side {
+            PawSide::Left => self.left_steering,
+            PawSide::Right => self.right_steering,
+        }
+    }
+}
+
+// Screen positions of everything touching the window this frame. A held mouse button counts as
+// a touch. Touches already drive the mouse on touch screens, so only when there are none
+pub fn screen_touches() -> Vec<Vec2> {
+    let mut positions: Vec<Vec2> = touches().iter().map(|touch| touch.position).collect();
+    if positions.is_empty() && is_mouse_button_down(MouseButton::Left) {
+        positions.push(mouse_position().into());
+    }
+    positions
+}
+
+// Where a touch at `game_point` counts as being on the playfield, or nothing for one beside it.
+// Ones above or below it count as being on its edge, so fingers can steer from the bars on a tall
+// screen
+pub fn field_touch(game_point: Vec2, game_area: &GameArea) -> Option<Vec2> {
+    (game_point.x > 0.0 && game_point.x < game_area.shape.x)
+        .then(|| game_area.clamp_to_field(game_point))
+}
+
+// Where a touch at `screen_point` steers from in game units, or nothing for one beside the field
+// or held on the pause gesture's hold spot
+pub fn steering_touch(
+    screen_point: Vec2,
+    game_area: &GameArea,
+    pause_gesture: &PauseGesture,
+) -> Option<Vec2> {
+    field_touch(game_area.screen_to_game(screen_point), game_area)
+        .filter(|touch| !pause_gesture.claims(*touch, game_area))
+}
+
+// Which paw a touch on the field steers, or nothing for one on the mute or pause button or right
+// on the center line. Mirrored, each half steers the paw on the other side
+pub fn steered_paw(touch: Vec2, game_shape: Vec2, mirrored: bool) -> Option<PawSide> {
+    if MUTE_BUTTON.contains(touch) || PAUSE_BUTTON.contains(touch) {
+        return None;
+    }
+    let half = game_shape.x / 2.0;
+    let left_half = if touch.x < half {
+        true
+    } else if touch.x > half {
+        false
+    } else {
+        return None;
+    };
+    Some(if left_half != mirrored {
+        PawSide::Left
+    } else {
+        PawSide::Right
+    })
+}
+
+// Keyboard input works alongside a gamepad, and a stick pushed all the way is as strong as
+// holding a key
+fn steering(paw_side: PawSide, gamepads: &Gamepads) -> f32 {
+    let (left_key, right_key) = paw_side.keys();
+    let mut steering = gamepads.paw_input(paw_side);
+    if is_key_down(left_key) {
+        steering -= 1.0;
+    }
+    if is_key_down(right_key) {
+        steering += 1.0;
+    }
+    steering
+}
+
+// How a paw follows several touches on its half at once, for players who rest more than one
+// finger there
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TouchStrategy {
+    // Chases the touch closest to the paw
+    Nearest,
+    // Chases the point between all the touches
+    Average,
+}
+
+impl TouchStrategy {
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "nearest" => Some(Self::Nearest),
+            "average" => Some(Self::Average),
+            _ => None,
+        }
+    }
+
+    // Where a paw at paw_x should head across its touches, or nothing without any
+    pub fn target_x(self, touches: impl Iterator<Item = Vec2>, paw_x: f32) -> Option<f32> {
+        match self {
+            Self::Nearest => touches
+                .map(|touch| touch.x)
+                .min_by(|a, b| (a - paw_x).abs().total_cmp(&(b - paw_x).abs())),
+            Self::Average => {
+                let (sum, count) =
+                    touches.fold((0.0, 0), |(sum, count), touch| (sum + touch.x, count + 1));
+                (count > 0).then(|| sum / count as f32)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_area::tests::game_area;
+
+    fn touches(xs: &[f32]) -> impl Iterator<Item = Vec2> + '_ {
+        xs.iter().map(|&x| Vec2 { x, y: 90.0 })
+    }
+
+    #[test]
+    fn nearest_chases_the_touch_closest_to_the_paw() {
+        let xs = [5.0, 22.0, 40.0];
+        assert_eq!(
+            TouchStrategy::Nearest.target_x(touches(&xs), 25.0),
+            Some(22.0)
+        );
+        assert_eq!(
+            TouchStrategy::Nearest.target_x(touches(&xs), 36.0),
+            Some(40.0)
+        );
+        assert_eq!(
+            TouchStrategy::Nearest.target_x(touches(&xs), 0.0),
+            Some(5.0)
+        );
+    }
+
+    #[test]
+    fn average_chases_the_middle_of_the_touches() {
+        let xs = [10.0, 20.0, 45.0];
+        assert_eq!(
+            TouchStrategy::Average.target_x(touches(&xs), 0.0),
+            Some(25.0)
+        );
+        // Wherever the paw is
+        assert_eq!(
+            TouchStrategy::Average.target_x(touches(&xs), 40.0),
+            Some(25.0)
+        );
+    }
+
+    #[test]
+    fn no_touches_means_no_target() {
+        for strategy in [TouchStrategy::Nearest, TouchStrategy::Average] {
+            assert_eq!(strategy.target_x(touches(&[]), 25.0), None);
+        }
+    }
+
+    const GAME_SHAPE: Vec2 = Vec2 { x: 100.0, y: 100.0 };
+
+    #[test]
+    fn touches_on_the_sides_of_the_field_are_beside_it() {
+        let game_area = game_area(GAME_SHAPE, Rect::default());
+        assert_eq!(field_touch(Vec2::new(0.0, 50.0), &game_area), None);
+        assert_eq!(field_touch(Vec2::new(100.0, 50.0), &game_area), None);
+        assert_eq!(field_touch(Vec2::new(-5.0, 50.0), &game_area), None);
+        assert_eq!(
+            field_touch(Vec2::new(0.1, 50.0), &game_area),
+            Some(Vec2::new(0.1, 50.0))
+        );
+        assert_eq!(
+            field_touch(Vec2::new(99.9, 50.0), &game_area),
+            Some(Vec2::new(99.9, 50.0))
+        );
+    }
+
+    #[test]
+    fn touches_above_or_below_the_field_count_on_its_edge() {
+        let game_area = game_area(GAME_SHAPE, Rect::default());
+        assert_eq!(
+            field_touch(Vec2::new(30.0, -20.0), &game_area),
+            Some(Vec2::new(30.0, 0.0))
+        );
+        assert_eq!(
+            field_touch(Vec2::new(30.0, 130.0), &game_area),
+            Some(Vec2::new(30.0, 100.0))
+        );
+    }
+
+    #[test]
+    fn touches_either_side_of_the_midline_steer_that_sides_paw() {
+        let steered = |x| steered_paw(Vec2::new(x, 80.0), GAME_SHAPE, false);
+        assert!(steered(50.0).is_none());
+        assert!(steered(49.9) == Some(PawSide::Left));
+        assert!(steered(50.1) == Some(PawSide::Right));
+        assert!(steered(0.1) == Some(PawSide::Left));
+        assert!(steered(99.9) == Some(PawSide::Right));
+    }
+
+    #[test]
+    fn mirrored_touches_steer_the_other_sides_paw() {
+        let steered = |x| steered_paw(Vec2::new(x, 80.0), GAME_SHAPE, true);
+        assert!(steered(50.0).is_none());
+        assert!(steered(49.9) == Some(PawSide::Right));
+        assert!(steered(50.1) == Some(PawSide::Left));
+    }
+
+    #[test]
+    fn touches_on_the_buttons_steer_nothing() {
+        for button in [MUTE_BUTTON, PAUSE_BUTTON] {
+            for mirrored in [false, true] {
+                assert!(steered_paw(button.center(), GAME_SHAPE, mirrored).is_none());
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::game_area::{GameArea, TranslateType};
+use crate::storage::{load_value, save_value};
+
+const LANDING_MARKER_KEY: &str = "landing_marker";
+// The marker is a faint bar this many game units across, sat on the paws' edge
+const MARKER_SIZE: Vec2 = Vec2 { x: 6.0, y: 1.0 };
+const MARKER_ALPHA: f32 = 0.4;
+
+// An assist showing where each ball will come down to the paws, so new players know where to go.
+// Off unless it's been turned on in settings
+pub struct LandingMarker {
+    enabled: bool,
+}
+
+impl LandingMarker {
+    pub fn new() -> Self {
+        Self {
+            enabled: load_value(LANDING_MARKER_KEY).as_deref() == Some("true"),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(LANDING_MARKER_KEY, &self.enabled.to_string());
+    }
+
+    // `paw_line` is the y of the paws' edge the balls land on
+    pub fn draw(&self, game_area: &GameArea, balls: &[Ball], paw_line: f32) {
+        if !self.enabled {
+            return;
+        }
+        let size = game_area.game_to_screen(MARKER_SIZE, TranslateType::JustScale);
+        let color = Color {
+            a: MARKER_ALPHA,
+            ..game_area.theme.text()
+        };
+        for x in balls
+            .iter()
+            .filter_map(|ball| ball.physics.predict_landing(paw_line))
+        {
+            let center = game_area.game_to_screen(Vec2 { x, y: paw_line }, TranslateType::Normal);
+            draw_rectangle(
+                center.x - size.x / 2.0,
+                center.y - size.y / 2.0,
+                size.x,
+                size.y,
+                color,
+            );
+        }
+    }
+}
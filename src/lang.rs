@@ -0,0 +1,251 @@
+use crate::storage::{load_value, save_value};
+
+const LANG_KEY: &str = "lang";
+
+// Language the UI is shown in
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lang {
+    English,
+    Spanish,
+}
+
+// Every piece of UI text, looked up with Lang::get
+#[derive(Clone, Copy)]
+pub enum Text {
+    TapToStart,
+    ChaosMode,
+    RightPaw,
+    LeftPaw,
+    Player,
+    Computer,
+    Difficulty,
+    Easy,
+    Normal,
+    Hard,
+    HighScores,
+    NoScoresYet,
+    TapToGoBack,
+    Settings,
+    Paused,
+    TapToResume,
+    GameOver,
+    Score,
+    Best,
+    BestScore,
+    NewBest,
+    ComboLost,
+    Retry,
+    Menu,
+    Music,
+    Effects,
+    Sound,
+    On,
+    Off,
+    Theme,
+    DefaultTheme,
+    HighContrastTheme,
+    FrameRateCap,
+    Vibration,
+    Language,
+    Back,
+    Mode,
+    Bounces,
+    Survival,
+    Practice,
+    Zen,
+    Time,
+    BestTime,
+    TiltSteering,
+    CalibrateTilt,
+    Paws,
+    AtTheBottom,
+    AtTheTop,
+    LandingMarker,
+    PixelArt,
+    PauseGesture,
+    MirrorControls,
+    Controls,
+    Accessibility,
+    SteadySpeed,
+    PawResponse,
+    Linear,
+    Smooth,
+    Snappy,
+}
+
+impl Lang {
+    const ALL: [Lang; 2] = [Lang::English, Lang::Spanish];
+
+    // The last chosen language, or English if there isn't a saved one
+    pub fn load() -> Self {
+        let saved = load_value(LANG_KEY);
+        Self::ALL
+            .into_iter()
+            .find(|lang| saved.as_deref() == Some(lang.key()))
+            .unwrap_or(Lang::English)
+    }
+
+    pub fn save(&self) {
+        save_value(LANG_KEY, self.key());
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::Spanish => "es",
+        }
+    }
+
+    // Each language's name in itself, so it can be found by someone who can't read the current one
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lang::English => "English",
+            Lang::Spanish => "Español",
+        }
+    }
+
+    // The language after this one, wrapping back round to the first
+    pub fn next(&self) -> Self {
+        match self {
+            Lang::English => Lang::Spanish,
+            Lang::Spanish => Lang::English,
+        }
+    }
+
+    // Text missing from a translation is shown in English
+    pub fn get(&self, text: Text) -> &'static str {
+        match self {
+            Lang::English => None,
+            Lang::Spanish => spanish(text),
+        }
+        .unwrap_or_else(|| english(text))
+    }
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::TapToStart => "Tap to start",
+        Text::ChaosMode => "Chaos mode",
+        Text::RightPaw => "Right paw",
+        Text::LeftPaw => "Left paw",
+        Text::Player => "player",
+        Text::Computer => "computer",
+        Text::Difficulty => "Difficulty",
+        Text::Easy => "Easy",
+        Text::Normal => "Normal",
+        Text::Hard => "Hard",
+        Text::HighScores => "High scores",
+        Text::NoScoresYet => "No scores yet",
+        Text::TapToGoBack => "Tap to go back",
+        Text::Settings => "Settings",
+        Text::Paused => "Paused",
+        Text::TapToResume => "Tap to resume",
+        Text::GameOver => "Game Over",
+        Text::Score => "Score",
+        Text::Best => "Best",
+        Text::BestScore => "Best Score",
+        Text::NewBest => "NEW BEST!",
+        Text::ComboLost => "Combo lost",
+        Text::Retry => "Retry",
+        Text::Menu => "Menu",
+        Text::Music => "Music",
+        Text::Effects => "Effects",
+        Text::Sound => "Sound",
+        Text::On => "on",
+        Text::Off => "off",
+        Text::Theme => "Theme",
+        Text::DefaultTheme => "Default",
+        Text::HighContrastTheme => "High contrast",
+        Text::FrameRateCap => "Frame rate cap",
+        Text::Vibration => "Vibration",
+        Text::Language => "Language",
+        Text::Back => "Back",
+        Text::Mode => "Mode",
+        Text::Bounces => "bounces",
+        Text::Survival => "survival",
+        Text::Practice => "practice",
+        Text::Zen => "zen",
+        Text::Time => "Time",
+        Text::BestTime => "Best Time",
+        Text::TiltSteering => "Tilt steering",
+        Text::CalibrateTilt => "Hold level and tap to calibrate",
+        Text::Paws => "Paws",
+        Text::AtTheBottom => "at the bottom",
+        Text::AtTheTop => "at the top",
+        Text::LandingMarker => "Landing marker",
+        Text::PixelArt => "Pixel art",
+        Text::PauseGesture => "Pause gestures",
+        Text::MirrorControls => "Cross-handed",
+        Text::Controls => "Controls",
+        Text::Accessibility => "Accessibility",
+        Text::SteadySpeed => "Steady ball speed",
+        Text::PawResponse => "Paw response",
+        Text::Linear => "linear",
+        Text::Smooth => "smooth",
+        Text::Snappy => "snappy",
+    }
+}
+
+fn spanish(text: Text) -> Option<&'static str> {
+    Some(match text {
+        Text::TapToStart => "Toca para empezar",
+        Text::ChaosMode => "Modo caos",
+        Text::RightPaw => "Pata derecha",
+        Text::LeftPaw => "Pata izquierda",
+        Text::Player => "jugador",
+        Text::Computer => "ordenador",
+        Text::Difficulty => "Dificultad",
+        Text::Easy => "Fácil",
+        Text::Normal => "Normal",
+        Text::Hard => "Difícil",
+        Text::HighScores => "Récords",
+        Text::NoScoresYet => "Aún no hay puntuaciones",
+        Text::TapToGoBack => "Toca para volver",
+        Text::Settings => "Ajustes",
+        Text::Paused => "Pausa",
+        Text::TapToResume => "Toca para seguir",
+        Text::GameOver => "Fin del juego",
+        Text::Score => "Puntos",
+        Text::Best => "Récord",
+        Text::BestScore => "Récord",
+        Text::NewBest => "¡NUEVO RÉCORD!",
+        Text::ComboLost => "Combo perdido",
+        Text::Retry => "Reintentar",
+        Text::Menu => "Menú",
+        Text::Music => "Música",
+        Text::Effects => "Efectos",
+        Text::Sound => "Sonido",
+        Text::On => "sí",
+        Text::Off => "no",
+        Text::Theme => "Tema",
+        Text::DefaultTheme => "Normal",
+        Text::HighContrastTheme => "Alto contraste",
+        Text::FrameRateCap => "Límite de FPS",
+        Text::Vibration => "Vibración",
+        Text::Language => "Idioma",
+        Text::Back => "Volver",
+        Text::Mode => "Modo",
+        Text::Bounces => "rebotes",
+        Text::Survival => "supervivencia",
+        Text::Practice => "práctica",
+        Text::Zen => "zen",
+        Text::Time => "Tiempo",
+        Text::BestTime => "Mejor tiempo",
+        Text::TiltSteering => "Inclinación",
+        Text::CalibrateTilt => "Ponlo recto y toca para calibrar",
+        Text::Paws => "Patas",
+        Text::AtTheBottom => "abajo",
+        Text::AtTheTop => "arriba",
+        Text::LandingMarker => "Marcar dónde cae",
+        Text::PixelArt => "Píxeles nítidos",
+        Text::PauseGesture => "Gestos para pausar",
+        Text::MirrorControls => "Manos cruzadas",
+        Text::Controls => "Controles",
+        Text::Accessibility => "Accesibilidad",
+        Text::SteadySpeed => "Velocidad constante",
+        Text::PawResponse => "Respuesta de patas",
+        Text::Linear => "lineal",
+        Text::Smooth => "suave",
+        Text::Snappy => "rápida",
+    })
+}
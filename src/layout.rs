@@ -0,0 +1,60 @@
+use crate::lang::Text;
+use crate::storage::{load_value, save_value};
+
+const LAYOUT_KEY: &str = "layout";
+
+// Which edge the paws defend. With them along the top the ball falls upwards, and is lost off
+// the top instead of the bottom
+#[derive(Clone, Copy, PartialEq)]
+pub enum Layout {
+    PawsAtBottom,
+    PawsAtTop,
+}
+
+impl Layout {
+    const ALL: [Layout; 2] = [Layout::PawsAtBottom, Layout::PawsAtTop];
+
+    // The last chosen layout, or paws at the bottom if there isn't a saved one
+    pub fn load() -> Self {
+        let saved = load_value(LAYOUT_KEY);
+        Self::ALL
+            .into_iter()
+            .find(|layout| saved.as_deref() == Some(layout.key()))
+            .unwrap_or(Layout::PawsAtBottom)
+    }
+
+    pub fn save(&self) {
+        save_value(LAYOUT_KEY, self.key());
+    }
+
+    // Name used for this in saved values
+    pub fn key(&self) -> &'static str {
+        match self {
+            Layout::PawsAtBottom => "bottom",
+            Layout::PawsAtTop => "top",
+        }
+    }
+
+    pub fn label(&self) -> Text {
+        match self {
+            Layout::PawsAtBottom => Text::AtTheBottom,
+            Layout::PawsAtTop => Text::AtTheTop,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Layout::PawsAtBottom => Layout::PawsAtTop,
+            Layout::PawsAtTop => Layout::PawsAtBottom,
+        }
+    }
+
+    // The sign of vertical movement towards the paws, which is down the screen unless they're
+    // at the top
+    pub fn towards_paws(&self) -> f32 {
+        match self {
+            Layout::PawsAtBottom => 1.0,
+            Layout::PawsAtTop => -1.0,
+        }
+    }
+}
@@ -1,364 +1,952 @@
+use macroquad::audio::{load_sound, Sound};
 use macroquad::prelude::*;
 
-const PAW_ACCELERATION: f32 = 5.0;
-const PAW_FRICTION: f32 = -0.2;
-const PAW_SHAPE: Vec2 = Vec2 {
-    x: 20.0 / 1.5,
-    y: 30.0 / 1.5,
+mod attract_mode;
+mod ball;
+mod ball_physics;
+mod config;
+mod danger_flash;
+mod date;
+mod debug_overlay;
+mod difficulty;
+mod fixed_step;
+mod focus;
+mod fps_counter;
+mod frame_limiter;
+mod game_area;
+mod gamepad;
+mod haptics;
+mod high_scores;
+mod input_recording;
+mod input_state;
+mod landing_marker;
+mod lang;
+mod layout;
+mod menu_animation;
+mod mirror_controls;
+mod obstacle;
+mod pause_button;
+mod pause_gesture;
+mod paw;
+mod pixel_art;
+mod power_up;
+mod replay;
+mod response_curve;
+mod score_event;
+mod score_popups;
+mod scores;
+mod screen_shake;
+mod screenshot;
+mod settings;
+mod slow_motion;
+mod sounds;
+mod sparks;
+mod steady_speed;
+mod storage;
+mod theme;
+mod tilt;
+
+use attract_mode::AttractMode;
+use ball::{play_events, Ball};
+use config::Config;
+use danger_flash::DangerFlash;
+use debug_overlay::DebugOverlay;
+use difficulty::Difficulty;
+use fixed_step::{FixedStep, FIXED_STEP};
+use focus::focus_lost;
+use fps_counter::FpsCounter;
+use frame_limiter::FrameLimiter;
+use game_area::{Drawable, GameArea, Sprite};
+use gamepad::Gamepads;
+use haptics::Haptics;
+use high_scores::HighScores;
+use input_recording::InputRecording;
+use input_state::InputState;
+use landing_marker::LandingMarker;
+use lang::Text;
+use layout::Layout;
+use menu_animation::MenuAnimation;
+use mirror_controls::MirrorControls;
+use obstacle::Obstacle;
+use pause_button::{draw_pause_button, PAUSE_BUTTON};
+use pause_gesture::PauseGesture;
+use paw::{Paw, PawController, PawSide};
+use pixel_art::PixelArt;
+use power_up::PowerUp;
+use replay::Replay;
+use response_curve::ResponseCurve;
+use score_popups::ScorePopups;
+use scores::{format_duration, ScoreMode, Scores};
+use screen_shake::ScreenShake;
+use screenshot::take_screenshot;
+use settings::Settings;
+use slow_motion::SlowMotion;
+use sounds::{Sounds, MUTE_BUTTON};
+use sparks::Sparks;
+use steady_speed::SteadySpeed;
+use storage::{load_value, save_value};
+use tilt::Tilt;
+
+// The field heats up as the score climbs, all the way by this score
+const HEAT_MAX_SCORE: f32 = 150.0;
+const FULLSCREEN_KEY: &str = "fullscreen";
+// Number of balls in play at once in chaos mode
+const CHAOS_BALLS: usize = 3;
+// Tapping the chaos mode line on the menu toggles it instead of starting the game
+const CHAOS_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 53.0,
+    w: 60.0,
+    h: 7.0,
 };
 
-const BALL_SHAPE: Vec2 = Vec2 { x: 10.0, y: 10.0 };
-const BASE_BALL_VELOCITY: f32 = 0.4;
+// Tapping the right paw line on the menu hands that paw to the computer or back
+const AI_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 60.0,
+    w: 60.0,
+    h: 7.0,
+};
+
+// Tapping the difficulty line on the menu moves on to the next difficulty
+const DIFFICULTY_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 67.0,
+    w: 60.0,
+    h: 7.0,
+};
+
+// Tapping the mode line on the menu switches between scoring bounces and survival time
+const MODE_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 74.0,
+    w: 60.0,
+    h: 7.0,
+};
+const HIGH_SCORES_BUTTON: Rect = Rect {
+    x: 20.0,
+    y: 81.0,
+    w: 60.0,
+    h: 7.0,
+};
+const SETTINGS_BUTTON: Rect = Rect {
+    x: 20.0,
+    y: 88.0,
+    w: 60.0,
+    h: 7.0,
+};
 
-const GAME_SHAPE: Vec2 = Vec2 { x: 100.0, y: 100.0 };
+// Buttons on the game over screen, only tapping these leaves it
+const RETRY_BUTTON: Rect = Rect {
+    x: 18.0,
+    y: 64.0,
+    w: 30.0,
+    h: 10.0,
+};
+const MENU_BUTTON: Rect = Rect {
+    x: 52.0,
+    y: 64.0,
+    w: 30.0,
+    h: 10.0,
+};
 
-trait Drawable {
-    fn draw(&self, game_area: &GameArea);
+enum GameState {
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+    Settings,
+    HighScores,
 }
 
-enum TranslateType {
-    Normal,
-    JustScale,
+// Screen position of a touch or click that started this frame
+fn tap_position() -> Option<Vec2> {
+    touches()
+        .iter()
+        .find(|touch| touch.phase == TouchPhase::Started)
+        .map(|touch| touch.position)
+        .or_else(|| is_mouse_button_pressed(MouseButton::Left).then(|| mouse_position().into()))
 }
 
-struct GameArea {
-    rect: Rect,
-    texture: Texture2D,
+// A fresh set of balls waiting to launch, several of them in chaos mode
+fn new_balls(
+    texture: &Texture2D,
+    config: &Config,
+    difficulty: Difficulty,
+    layout: Layout,
+    chaos_mode: bool,
+) -> Vec<Ball> {
+    let count = if chaos_mode { CHAOS_BALLS } else { 1 };
+    (0..count)
+        .map(|_| Ball::new(texture.clone(), config, difficulty, layout))
+        .collect()
 }
 
-impl GameArea {
-    fn new(texture: Texture2D) -> Self {
-        Self {
-            rect: Rect::default(),
-            texture,
-        }
-    }
+// Textures are baked into the binary so there's nothing to go missing at runtime. With the
+// runtime_assets feature they're read from assets/ instead so they can be swapped without
+// rebuilding, and `$placeholder` fills in for any that can't be loaded
+#[cfg(not(feature = "runtime_assets"))]
+macro_rules! game_texture {
+    ($path:literal, $placeholder:expr) => {
+        Texture2D::from_file_with_format(include_bytes!(concat!("../", $path)), None)
+    };
+}
 
-    fn update(&mut self) {
-        let screen_size = Vec2 {
-            x: screen_width(),
-            y: screen_height(),
-        };
-        let min_axis = screen_size.min_element();
-        self.rect.x = screen_size.x / 2.0 - min_axis / 2.0;
-        self.rect.y = screen_size.y / 2.0 - min_axis / 2.0;
-        self.rect.w = min_axis;
-        self.rect.h = min_axis;
-    }
+#[cfg(feature = "runtime_assets")]
+macro_rules! game_texture {
+    ($path:literal, $placeholder:expr) => {
+        load_texture_or_placeholder($path, $placeholder).await
+    };
+}
 
-    fn draw(&self) {
-        draw_texture_ex(
-            &self.texture,
-            self.rect.x,
-            self.rect.y,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2 {
-                    x: self.rect.w,
-                    y: self.rect.h,
-                }),
-                ..Default::default()
-            },
-        );
+// The obstacles the difficulty plays with, between the launch point and the wall across from the
+// paws
+fn new_obstacles(config: &Config, difficulty: Difficulty, layout: Layout) -> Vec<Obstacle> {
+    if !difficulty.has_obstacles() {
+        return Vec::new();
     }
-
-    fn game_to_screen(&self, game_point: Vec2, translate_type: TranslateType) -> Vec2 {
-        let current_shape = Vec2 {
-            x: self.rect.w,
-            y: self.rect.h,
-        };
-        let offset = Vec2 {
-            x: self.rect.x,
-            y: self.rect.y,
-        };
-        match translate_type {
-            TranslateType::Normal => ((current_shape / GAME_SHAPE) * game_point) + offset,
-            TranslateType::JustScale => (current_shape / GAME_SHAPE) * game_point,
-        }
+    let obstacles = [Obstacle::center(), Obstacle::moving()]
+        .into_iter()
+        .map(|obstacle| obstacle.centered(config.game_shape.x));
+    match layout {
+        Layout::PawsAtBottom => obstacles.collect(),
+        Layout::PawsAtTop => obstacles
+            .map(|obstacle| obstacle.flipped(config.game_shape.y))
+            .collect(),
     }
+}
 
-    fn screen_to_game(&self, screen_point: Vec2) -> Vec2 {
-        let current_shape = Vec2 {
-            x: self.rect.w,
-            y: self.rect.h,
-        };
-        let offset = Vec2 {
-            x: self.rect.x,
-            y: self.rect.y,
-        };
-        ((screen_point - offset) / current_shape) * GAME_SHAPE
+// A missing texture shouldn't stop the game, so it gets replaced by a solid color
+#[cfg(feature = "runtime_assets")]
+async fn load_texture_or_placeholder(path: &str, color: Color) -> Texture2D {
+    match load_texture(path).await {
+        Ok(texture) => texture,
+        Err(error) => {
+            warn!("Could not load {}, using a placeholder: {}", path, error);
+            Texture2D::from_image(&Image::gen_image_color(1, 1, color))
+        }
     }
 }
 
-enum PawSide {
-    Left,
-    Right,
+// A missing or broken sound is left silent rather than stopping the game
+async fn load_sound_or_silence(path: &str) -> Option<Sound> {
+    match load_sound(path).await {
+        Ok(sound) => Some(sound),
+        Err(error) => {
+            warn!("Could not load {}, leaving it silent: {}", path, error);
+            None
+        }
+    }
 }
 
-struct Paw {
-    rect: Rect,
-    velocity: Vec2,
-    paw_side: PawSide,
-    texture: Texture2D,
+// A square window to fit the square playfield, though it can be resized to anything. Whether it
+// starts fullscreen is remembered from the last time F11 was pressed
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "Cat Ball Wow!".to_string(),
+        window_width: 700,
+        window_height: 700,
+        fullscreen: load_value(FULLSCREEN_KEY).as_deref() == Some("true"),
+        ..Default::default()
+    }
 }
 
-impl Paw {
-    fn new(texture: Texture2D, paw_side: PawSide) -> Self {
-        Self {
-            rect: Rect {
-                x: match paw_side {
-                    PawSide::Left => 25.0,
-                    PawSide::Right => 75.0 - PAW_SHAPE.x,
-                },
-                y: GAME_SHAPE.y - PAW_SHAPE.y,
-                w: PAW_SHAPE.x,
-                h: PAW_SHAPE.y,
-            },
-            velocity: Vec2::ZERO,
-            paw_side,
-            texture,
+#[macroquad::main(window_conf)]
+async fn main() {
+    rand::srand(miniquad::date::now() as u64);
+    let config = Config::load().await;
+
+    // Load textures, filtered smoothly unless pixel art mode is on
+    let ball_texture = game_texture!("assets/ball.png", WHITE);
+    let background_texture = game_texture!("assets/background.png", BEIGE);
+    let left_paw_texture = game_texture!("assets/paw_left.png", DARKGRAY);
+    let right_paw_texture = game_texture!("assets/paw_right.png", DARKGRAY);
+    let power_up_texture = game_texture!("assets/power_up.png", GOLD);
+    let mut pixel_art = PixelArt::new(vec![
+        ball_texture.clone(),
+        background_texture.clone(),
+        left_paw_texture.clone(),
+        right_paw_texture.clone(),
+        power_up_texture.clone(),
+    ]);
+
+    // The score has its own font, falling back to the default one if it's missing
+    let score_font = match load_ttf_font("assets/score_font.ttf").await {
+        Ok(font) => Some(font),
+        Err(error) => {
+            warn!(
+                "Could not load the score font, using the default: {}",
+                error
+            );
+            None
         }
+    };
+
+    // Load sounds
+    let wall_sound = load_sound_or_silence("assets/wall.wav").await;
+    let paw_sound = load_sound_or_silence("assets/paw.wav").await;
+    let lose_sound = load_sound_or_silence("assets/lose.wav").await;
+    let combo_break_sound = load_sound_or_silence("assets/combo_break.wav").await;
+    let music = load_sound_or_silence("assets/music.wav").await;
+
+    // Create game objects
+    let mut game_area = GameArea::new(background_texture, &config);
+    let mut difficulty = Difficulty::load();
+    let mut response_curve = ResponseCurve::load();
+    let mut left_paw = Paw::new(
+        left_paw_texture.clone(),
+        PawSide::Left,
+        &config,
+        difficulty,
+        game_area.layout,
+        response_curve,
+    );
+    let mut right_paw = Paw::new(
+        right_paw_texture.clone(),
+        PawSide::Right,
+        &config,
+        difficulty,
+        game_area.layout,
+        response_curve,
+    );
+    let mut mirror_controls = MirrorControls::new();
+    left_paw.set_mirrored(mirror_controls.is_enabled());
+    right_paw.set_mirrored(mirror_controls.is_enabled());
+    let mut obstacles = new_obstacles(&config, difficulty, game_area.layout);
+    let mut chaos_mode = false;
+    let mut balls = new_balls(
+        &ball_texture,
+        &config,
+        difficulty,
+        game_area.layout,
+        chaos_mode,
+    );
+    let mut power_up = PowerUp::new(power_up_texture, &config);
+    let mut scores = Scores::new(difficulty, score_font);
+    if let Some(hook) = score_event::page_hook() {
+        scores.set_event_hook(hook);
     }
+    let mut replay = Replay::new(
+        ball_texture.clone(),
+        &config,
+        difficulty,
+        scores.mode,
+        game_area.layout,
+    );
+    let mut score_popups = ScorePopups::new();
+    let mut sparks = Sparks::new();
+    let mut high_scores = HighScores::new();
+    let mut sounds = Sounds::new(wall_sound, paw_sound, lose_sound, combo_break_sound, music);
+    sounds.set_ambient(scores.mode == ScoreMode::Zen);
+    let mut attract_mode = AttractMode::new(
+        [
+            ball_texture.clone(),
+            left_paw_texture.clone(),
+            right_paw_texture.clone(),
+        ],
+        &config,
+        game_area.layout,
+        &sounds,
+    );
+    let mut menu_animation = MenuAnimation::new(
+        [ball_texture.clone(), left_paw_texture, right_paw_texture],
+        &config,
+        game_area.layout,
+    );
+    let mut game_state = GameState::Menu;
+    let mut debug_overlay = DebugOverlay::new();
+    let mut fps_counter = FpsCounter::new();
+    let mut screen_shake = ScreenShake::new();
+    let mut settings = Settings::new();
+    let mut frame_limiter = FrameLimiter::new();
+    let mut haptics = Haptics::new();
+    let mut tilt = Tilt::new();
+    let mut landing_marker = LandingMarker::new();
+    let mut steady_speed = SteadySpeed::new();
+    let mut danger_flash = DangerFlash::new(&config);
+    let mut pause_gesture = PauseGesture::new();
+    let mut fullscreen = load_value(FULLSCREEN_KEY).as_deref() == Some("true");
+    let mut gamepads = Gamepads::new();
+    let mut slow_motion = SlowMotion::new();
+    let mut fixed_step = FixedStep::new();
+    let mut input_recording = InputRecording::new();
+
+    loop {
+        // Nothing's updated or drawn while the window has no size, so no NaNs from mapping
+        // touches onto a playfield that isn't there reach the paws and balls. A game being
+        // played pauses, as the window's likely been minimized
+        if !game_area.update() {
+            if matches!(game_state, GameState::Playing) {
+                sounds.pause();
+                game_state = GameState::Paused;
+            }
+            frame_limiter.wait();
+            next_frame().await;
+            continue;
+        }
+        clear_background(game_area.theme.background());
+
+        gamepads.update();
+
+        // A tap on the mute button is used up by it and doesn't count as a tap for anything else
+        let mut tap = tap_position().map(|position| game_area.screen_to_game(position));
+        if tap.is_some_and(|tap| MUTE_BUTTON.contains(tap)) {
+            sounds.toggle_mute();
+            tap = None;
+        }
+        if is_key_pressed(KeyCode::M) {
+            sounds.toggle_mute();
+        }
+        // The pause button only does anything while there's a game to pause or resume
+        let pause_tapped = matches!(game_state, GameState::Playing | GameState::Paused)
+            && tap.is_some_and(|tap| PAUSE_BUTTON.contains(tap));
+        // Touches are followed in every state so the gesture knows about fingers put down before
+        // the game started or resumed, but it only pauses a game being played
+        let pause_gestured = pause_gesture.update(&game_area);
+        let tapped = tap.is_some();
+        // Space and a gamepad's A or Start do the same as tapping anywhere
+        let confirmed = is_key_pressed(KeyCode::Space) || gamepads.confirm_pressed();
+        debug_overlay.update();
+        fps_counter.update();
+
+        // Looking away pauses before the game moves on without the player, and it stays paused
+        // until they come back and resume
+        if matches!(game_state, GameState::Playing) && focus_lost() {
+            sounds.pause();
+            game_state = GameState::Paused;
+        }
 
-    fn update(&mut self, game_area: &GameArea) {
-        // Get all touch locations in game units
-        let mut touches = touches()
-            .iter()
-            .map(|point| game_area.screen_to_game(point.position))
-            .collect::<Vec<Vec2>>();
-
-        // Keep only the touches that should apply to this paw
-        touches.retain(|touch| {
-            touch.x > 0.0
-                && touch.x < GAME_SHAPE.x
-                && match self.paw_side {
-                    PawSide::Left => touch.x < GAME_SHAPE.x / 2.0,
-                    PawSide::Right => touch.x > GAME_SHAPE.x / 2.0,
+        // Only the playing state advances the game, every other state shows a frozen frame
+        match game_state {
+            GameState::Menu => {
+                // Anything at all stops the demo, and starts a game if the demo was showing
+                let any_input = tapped || confirmed || get_last_key_pressed().is_some();
+                let demo_running = attract_mode.is_running();
+                if any_input {
+                    attract_mode.stop();
                 }
-        });
-
-        // Apply acceleration in the direction of the closest touch
-        let mut paw_acceleration: f32 = 0.0;
-        let mut smallest_distance: f32 = f32::INFINITY;
-        for touch in touches {
-            let distance = (touch.x - self.rect.center().x).abs();
-            if distance < smallest_distance {
-                smallest_distance = distance;
-                if touch.x > self.rect.center().x {
-                    paw_acceleration = PAW_ACCELERATION / (1.0 / (distance / GAME_SHAPE.x));
+                if demo_running && any_input {
+                    sounds.start_music();
+                    game_state = GameState::Playing;
+                } else if is_key_pressed(KeyCode::C)
+                    || tap.is_some_and(|tap| CHAOS_TOGGLE.contains(tap))
+                {
+                    chaos_mode = !chaos_mode;
+                    balls = new_balls(
+                        &ball_texture,
+                        &config,
+                        difficulty,
+                        game_area.layout,
+                        chaos_mode,
+                    );
+                } else if is_key_pressed(KeyCode::P)
+                    || tap.is_some_and(|tap| AI_TOGGLE.contains(tap))
+                {
+                    right_paw.controller = match right_paw.controller {
+                        PawController::Human => PawController::Ai,
+                        PawController::Ai => PawController::Human,
+                    };
+                } else if is_key_pressed(KeyCode::L)
+                    || tap.is_some_and(|tap| DIFFICULTY_TOGGLE.contains(tap))
+                {
+                    difficulty = difficulty.next();
+                    difficulty.save();
+                    left_paw.set_difficulty(difficulty);
+                    right_paw.set_difficulty(difficulty);
+                    scores.difficulty = difficulty;
+                    scores.lives = difficulty.lives();
+                    obstacles = new_obstacles(&config, difficulty, game_area.layout);
+                    replay = Replay::new(
+                        ball_texture.clone(),
+                        &config,
+                        difficulty,
+                        scores.mode,
+                        game_area.layout,
+                    );
+                    balls = new_balls(
+                        &ball_texture,
+                        &config,
+                        difficulty,
+                        game_area.layout,
+                        chaos_mode,
+                    );
+                } else if is_key_pressed(KeyCode::T)
+                    || tap.is_some_and(|tap| MODE_TOGGLE.contains(tap))
+                {
+                    scores.mode = scores.mode.next();
+                    scores.mode.save();
+                    sounds.set_ambient(scores.mode == ScoreMode::Zen);
+                    replay = Replay::new(
+                        ball_texture.clone(),
+                        &config,
+                        difficulty,
+                        scores.mode,
+                        game_area.layout,
+                    );
+                } else if is_key_pressed(KeyCode::H)
+                    || tap.is_some_and(|tap| HIGH_SCORES_BUTTON.contains(tap))
+                {
+                    game_state = GameState::HighScores;
+                } else if is_key_pressed(KeyCode::S)
+                    || tap.is_some_and(|tap| SETTINGS_BUTTON.contains(tap))
+                {
+                    game_state = GameState::Settings;
+                } else if confirmed || tapped {
+                    sounds.start_music();
+                    game_state = GameState::Playing;
                 }
-                if touch.x < self.rect.center().x {
-                    paw_acceleration = -PAW_ACCELERATION / (1.0 / (distance / GAME_SHAPE.x));
+                input_recording.update();
+                attract_mode.update(&obstacles);
+                if !attract_mode.is_running() {
+                    menu_animation.update();
                 }
             }
-        }
-        paw_acceleration += self.velocity.x * PAW_FRICTION;
-        self.velocity += paw_acceleration;
-        self.rect.x += self.velocity.x + 0.5 * paw_acceleration;
-
-        // Clamp the paw's movement so it stays in the area it should
-        match self.paw_side {
-            PawSide::Left => {
-                self.rect.x = self.rect.x.clamp(0.0, (GAME_SHAPE.x / 2.0) - self.rect.w)
+            GameState::Playing => {
+                // Everything a recorded run's outcome depends on, checked before playing it back
+                let run_settings = format!(
+                    "{} {} chaos={} ai={} {} {} mirrored={} steady={}",
+                    difficulty.key(),
+                    scores.mode.key(),
+                    chaos_mode,
+                    matches!(right_paw.controller, PawController::Ai),
+                    game_area.layout.key(),
+                    response_curve.key(),
+                    mirror_controls.is_enabled(),
+                    steady_speed.is_enabled()
+                );
+                // A recorded run starts over from its own seed, so playing it back takes the
+                // same random turns
+                if let Some(seed) = input_recording.start_run(&run_settings) {
+                    rand::srand(seed);
+                    balls = new_balls(
+                        &ball_texture,
+                        &config,
+                        difficulty,
+                        game_area.layout,
+                        chaos_mode,
+                    );
+                    power_up.reset();
+                    obstacles = new_obstacles(&config, difficulty, game_area.layout);
+                    left_paw.reset();
+                    right_paw.reset();
+                    sparks.reset();
+                    slow_motion.reset();
+                    fixed_step.reset();
+                }
+                slow_motion.update();
+                let input = input_recording.input(InputState::from_frame(
+                    &game_area,
+                    &slow_motion,
+                    &gamepads,
+                    &tilt,
+                    &pause_gesture,
+                ));
+                // The physics only moves in whole fixed steps, however many fit in this frame
+                for _ in 0..fixed_step.advance(input.delta) {
+                    let step = InputState {
+                        delta: FIXED_STEP,
+                        ..input.clone()
+                    };
+                    for obstacle in obstacles.iter_mut() {
+                        obstacle.update(step.delta);
+                    }
+                    left_paw.update(&step, &balls);
+                    right_paw.update(&step, &balls);
+
+                    // All balls score into the same total, and lost ones leave play
+                    for ball in balls.iter_mut() {
+                        let events = ball.update(
+                            &[&left_paw, &right_paw],
+                            &obstacles,
+                            &step,
+                            &mut scores,
+                            &mut score_popups,
+                            &mut sparks,
+                            steady_speed.is_enabled(),
+                        );
+                        play_events(
+                            &events,
+                            [&mut left_paw, &mut right_paw],
+                            &mut sounds,
+                            &mut haptics,
+                            &mut screen_shake,
+                            &mut slow_motion,
+                        );
+                    }
+                    replay.update(&balls, step.delta);
+                    danger_flash.update(&balls, step.delta);
+                    balls.retain(|ball| !ball.physics.is_lost());
+                }
+                // Zen stays calm however high the score gets
+                if scores.mode != ScoreMode::Zen {
+                    game_area.heat = (scores.score as f32 / HEAT_MAX_SCORE).min(1.0);
+                }
+                scores.update(
+                    balls
+                        .first()
+                        .and_then(|ball| ball.physics.countdown_number())
+                        .is_none(),
+                    input.frame_time,
+                );
+                score_popups.update();
+                sparks.update(input.delta);
+
+                match power_up.update(&balls, input.frame_time) {
+                    Some(PawSide::Left) => left_paw.widen(),
+                    Some(PawSide::Right) => right_paw.widen(),
+                    None => {}
+                }
+
+                if balls.is_empty() {
+                    // Zen's slower ball would make for unfair entries in the table and ghosts
+                    // no one could race
+                    if scores.mode != ScoreMode::Zen {
+                        let result = match scores.mode {
+                            ScoreMode::Survival => scores.survival_time as u32,
+                            _ => scores.score,
+                        };
+                        high_scores.insert(result, difficulty, scores.mode);
+                        replay.end_run(result);
+                    }
+                    scores.end_run();
+                    input_recording.end_run(scores.score);
+                    game_area.heat = 0.0;
+                    game_state = GameState::GameOver;
+                } else if is_key_pressed(KeyCode::Escape)
+                    || gamepads.pause_pressed()
+                    || pause_tapped
+                    || pause_gestured
+                {
+                    sounds.pause();
+                    game_state = GameState::Paused;
+                }
             }
-            PawSide::Right => {
-                self.rect.x = self
-                    .rect
-                    .x
-                    .clamp(GAME_SHAPE.x / 2.0, GAME_SHAPE.x - self.rect.w)
+            GameState::Paused => {
+                if confirmed || is_key_pressed(KeyCode::Escape) || tapped {
+                    sounds.resume();
+                    game_state = GameState::Playing;
+                }
+            }
+            GameState::GameOver => {
+                let retry = confirmed || tap.is_some_and(|tap| RETRY_BUTTON.contains(tap));
+                let menu = is_key_pressed(KeyCode::Escape)
+                    || tap.is_some_and(|tap| MENU_BUTTON.contains(tap));
+                if retry || menu {
+                    balls = new_balls(
+                        &ball_texture,
+                        &config,
+                        difficulty,
+                        game_area.layout,
+                        chaos_mode,
+                    );
+                    power_up.reset();
+                    left_paw.reset();
+                    right_paw.reset();
+                    scores.reset();
+                    obstacles = new_obstacles(&config, difficulty, game_area.layout);
+                    replay.reset();
+                    score_popups.reset();
+                    sparks.reset();
+                    slow_motion.reset();
+                    fixed_step.reset();
+                    game_state = if retry {
+                        GameState::Playing
+                    } else {
+                        GameState::Menu
+                    };
+                }
+            }
+            GameState::HighScores => {
+                if is_key_pressed(KeyCode::Escape) || confirmed || tapped {
+                    game_state = GameState::Menu;
+                }
+            }
+            GameState::Settings => {
+                if settings.update(
+                    &mut game_area,
+                    &mut sounds,
+                    &mut frame_limiter,
+                    &mut haptics,
+                    &mut tilt,
+                    &mut landing_marker,
+                    &mut pixel_art,
+                    &mut pause_gesture,
+                    &mut response_curve,
+                    &mut mirror_controls,
+                    &mut steady_speed,
+                    tap,
+                ) {
+                    left_paw.set_response_curve(response_curve);
+                    right_paw.set_response_curve(response_curve);
+                    left_paw.set_mirrored(mirror_controls.is_enabled());
+                    right_paw.set_mirrored(mirror_controls.is_enabled());
+                    // The layout may have changed, so everything starts over along the right edge
+                    left_paw.set_layout(game_area.layout);
+                    right_paw.set_layout(game_area.layout);
+                    attract_mode.set_layout(game_area.layout);
+                    menu_animation.set_layout(game_area.layout);
+                    obstacles = new_obstacles(&config, difficulty, game_area.layout);
+                    replay = Replay::new(
+                        ball_texture.clone(),
+                        &config,
+                        difficulty,
+                        scores.mode,
+                        game_area.layout,
+                    );
+                    balls = new_balls(
+                        &ball_texture,
+                        &config,
+                        difficulty,
+                        game_area.layout,
+                        chaos_mode,
+                    );
+                    game_state = GameState::Menu;
+                }
             }
-        };
-    }
-}
-
-impl Drawable for Paw {
-    fn draw(&self, game_area: &GameArea) {
-        let screen_size = game_area.game_to_screen(self.rect.size(), TranslateType::JustScale);
-        let screen_position = game_area.game_to_screen(self.rect.point(), TranslateType::Normal);
-
-        draw_texture_ex(
-            &self.texture,
-            screen_position.x,
-            screen_position.y,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2 {
-                    x: screen_size.x,
-                    y: screen_size.y,
-                }),
-                ..Default::default()
-            },
-        );
-    }
-}
-
-struct Ball {
-    rect: Rect,
-    velocity: Vec2,
-    texture: Texture2D,
-}
-
-impl Ball {
-    fn new(texture: Texture2D) -> Self {
-        Self {
-            rect: Rect {
-                x: GAME_SHAPE.x / 2.0 - BALL_SHAPE.x / 2.0,
-                y: GAME_SHAPE.y / 2.0 - BALL_SHAPE.y / 2.0,
-                w: BALL_SHAPE.x,
-                h: BALL_SHAPE.y,
-            },
-            velocity: Vec2 {
-                x: BASE_BALL_VELOCITY,
-                y: BASE_BALL_VELOCITY,
-            },
-            texture,
         }
-    }
 
-    fn update(&mut self, paw_locations: Vec<Rect>, scores: &mut Scores) {
-        // calculate ball velocity
-        let ball_velocity =
-            BASE_BALL_VELOCITY + BASE_BALL_VELOCITY * ((scores.score + 1) as f32 / 100.0);
-        // Check for collision with walls
-        if self.rect.x < 0.0 {
-            self.velocity.x = ball_velocity;
-            scores.score += 1;
+        // Shake the playfield and everything in it, the letterbox hides whatever moves past its edges
+        screen_shake.update();
+        let game_rect = game_area.rect;
+        let shake_offset = screen_shake.offset(&game_area);
+        game_area.rect.x += shake_offset.x;
+        game_area.rect.y += shake_offset.y;
+        game_area.draw();
+        if scores.mode == ScoreMode::Zen {
+            draw_rectangle(
+                game_area.rect.x,
+                game_area.rect.y,
+                game_area.rect.w,
+                game_area.rect.h,
+                game_area.theme.zen_wash(),
+            );
         }
-        if (self.rect.x + self.rect.w) > GAME_SHAPE.x {
-            self.velocity.x = -ball_velocity;
-            scores.score += 1;
+        for obstacle in &obstacles {
+            obstacle.draw(&game_area);
         }
-        if self.rect.y < 0.0 {
-            self.velocity.y = ball_velocity;
-            scores.score += 1;
+        power_up.draw(&game_area);
+        if matches!(game_state, GameState::Playing | GameState::Paused) {
+            danger_flash.draw(&game_area);
+            scores.draw_safety_nets(&game_area);
+            landing_marker.draw(&game_area, &balls, left_paw.face_y());
         }
-        // Check for collision with paws
-        for paw_location in paw_locations {
-            if self.rect.contains(paw_location.center()) {
-                self.velocity.y = -ball_velocity;
-                scores.score += 1;
+        // The demo, or otherwise the hopping ball, takes the place of the real paws and balls
+        // behind the menu
+        if matches!(game_state, GameState::Menu) && attract_mode.is_running() {
+            attract_mode.draw(&game_area);
+        } else if matches!(game_state, GameState::Menu) {
+            menu_animation.draw(&game_area);
+        } else {
+            // Drawn part way from the last physics step towards the next, so they move smoothly
+            // whatever the frame rate
+            let alpha = fixed_step.alpha();
+            replay.draw(&game_area);
+            left_paw.draw_interpolated(&game_area, alpha);
+            right_paw.draw_interpolated(&game_area, alpha);
+            for ball in &balls {
+                ball.draw_interpolated(&game_area, alpha);
             }
+            sparks.draw(&game_area);
+            // Zen keeps the score out of sight until the end
+            if scores.mode != ScoreMode::Zen {
+                score_popups.draw(&game_area);
+            }
+        }
+        game_area.rect = game_rect;
+        game_area.draw_letterbox(BLACK);
+
+        scores.draw(&game_area);
+        sounds.draw(&game_area);
+        if matches!(game_state, GameState::Playing | GameState::Paused) {
+            draw_pause_button(&game_area, matches!(game_state, GameState::Paused));
         }
-        // Check end-game
-        if self.rect.y > GAME_SHAPE.y {
-            self.rect.x = GAME_SHAPE.x / 2.0 - BALL_SHAPE.x / 2.0;
-            self.rect.y = GAME_SHAPE.y / 2.0 - BALL_SHAPE.y / 2.0;
-            self.velocity = Vec2 {
-                x: BASE_BALL_VELOCITY,
-                y: BASE_BALL_VELOCITY,
-            };
-            scores.score = 0;
+        if matches!(game_state, GameState::Playing) {
+            pause_gesture.draw(&game_area);
         }
 
-        // Update position
-        self.rect.x += self.velocity.x;
-        self.rect.y += self.velocity.y;
-    }
-}
+        let text_color = game_area.theme.text();
+        let lang = game_area.lang;
+        let on_off = |on: bool| lang.get(if on { Text::On } else { Text::Off });
+        match game_state {
+            GameState::Menu => {
+                game_area.draw_centered_text("Cat Ball Wow!", 40.0, 15.0, text_color);
+                game_area.draw_centered_text(lang.get(Text::TapToStart), 50.0, 8.0, text_color);
+                game_area.draw_centered_text(
+                    &format!("{}: {}", lang.get(Text::ChaosMode), on_off(chaos_mode)),
+                    58.0,
+                    6.0,
+                    text_color,
+                );
+                let right_paw_controller = match right_paw.controller {
+                    PawController::Human => Text::Player,
+                    PawController::Ai => Text::Computer,
+                };
+                game_area.draw_centered_text(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::RightPaw),
+                        lang.get(right_paw_controller)
+                    ),
+                    65.0,
+                    6.0,
+                    text_color,
+                );
+                game_area.draw_centered_text(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::Difficulty),
+                        lang.get(difficulty.label())
+                    ),
+                    72.0,
+                    6.0,
+                    text_color,
+                );
+                game_area.draw_centered_text(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::Mode),
+                        lang.get(scores.mode.label())
+                    ),
+                    79.0,
+                    6.0,
+                    text_color,
+                );
+                game_area.draw_centered_text(lang.get(Text::HighScores), 86.0, 6.0, text_color);
+                game_area.draw_centered_text(lang.get(Text::Settings), 93.0, 6.0, text_color);
+            }
+            GameState::Playing => {
+                // Balls all launch together, so any of them has the countdown
+                if let Some(countdown_number) = balls
+                    .first()
+                    .and_then(|ball| ball.physics.countdown_number())
+                {
+                    game_area.draw_centered_text(
+                        &countdown_number.to_string(),
+                        38.0,
+                        25.0,
+                        text_color,
+                    );
+                }
+            }
+            GameState::Paused => {
+                game_area.draw_centered_text(lang.get(Text::Paused), 40.0, 15.0, text_color);
+                game_area.draw_centered_text(lang.get(Text::TapToResume), 55.0, 8.0, text_color);
+            }
+            GameState::GameOver => {
+                // Fade out the playfield so the final score stands out
+                draw_rectangle(
+                    game_area.rect.x,
+                    game_area.rect.y,
+                    game_area.rect.w,
+                    game_area.rect.h,
+                    game_area.theme.overlay(0.6),
+                );
+                game_area.draw_centered_text(lang.get(Text::GameOver), 35.0, 15.0, text_color);
+                // Practice never ends, so only ever gets here from the other modes
+                let (result_text, best_text) = match scores.mode {
+                    ScoreMode::Bounces | ScoreMode::Practice => (
+                        format!("{}: {}", lang.get(Text::Score), scores.score),
+                        format!("{}: {}", lang.get(Text::Best), scores.best_score()),
+                    ),
+                    ScoreMode::Zen => (
+                        format!("{}: {}", lang.get(Text::Score), scores.score),
+                        format!("{}: {}", lang.get(Text::Best), scores.best_zen_score()),
+                    ),
+                    ScoreMode::Survival => (
+                        format!(
+                            "{}: {}",
+                            lang.get(Text::Time),
+                            format_duration(scores.survival_time)
+                        ),
+                        format!(
+                            "{}: {}",
+                            lang.get(Text::Best),
+                            format_duration(scores.best_time())
+                        ),
+                    ),
+                };
+                game_area.draw_centered_text(&result_text, 48.0, 8.0, text_color);
+                game_area.draw_centered_text(&best_text, 57.0, 8.0, text_color);
+                game_area.draw_button(lang.get(Text::Retry), RETRY_BUTTON, 7.0, text_color);
+                game_area.draw_button(lang.get(Text::Menu), MENU_BUTTON, 7.0, text_color);
+            }
+            GameState::HighScores => high_scores.draw(&game_area, scores.mode),
+            GameState::Settings => settings.draw(
+                &game_area,
+                &sounds,
+                &frame_limiter,
+                &haptics,
+                &tilt,
+                &landing_marker,
+                &pixel_art,
+                &pause_gesture,
+                response_curve,
+                &mirror_controls,
+                &steady_speed,
+            ),
+        }
 
-impl Drawable for Ball {
-    fn draw(&self, game_area: &GameArea) {
-        let screen_size = game_area.game_to_screen(self.rect.size(), TranslateType::JustScale);
-        let screen_position = game_area.game_to_screen(self.rect.point(), TranslateType::Normal);
-
-        draw_texture_ex(
-            &self.texture,
-            screen_position.x,
-            screen_position.y,
-            WHITE,
-            DrawTextureParams {
-                dest_size: Some(Vec2 {
-                    x: screen_size.x,
-                    y: screen_size.y,
-                }),
-                ..Default::default()
-            },
+        debug_overlay.draw(
+            &game_area,
+            &fps_counter,
+            &balls,
+            &left_paw,
+            &right_paw,
+            &pause_gesture,
         );
-    }
-}
-
-struct Scores {
-    score: u32,
-    best_score: u32,
-}
+        input_recording.draw();
 
-impl Scores {
-    fn new() -> Self {
-        Self {
-            score: 0,
-            best_score: 0,
+        if is_key_pressed(KeyCode::F11) {
+            fullscreen = !fullscreen;
+            set_fullscreen(fullscreen);
+            save_value(FULLSCREEN_KEY, &fullscreen.to_string());
         }
-    }
 
-    fn update(&mut self) {
-        if self.score > self.best_score {
-            self.best_score = self.score
+        // F2 saves the frame with the score written along the bottom for sharing
+        if is_key_pressed(KeyCode::F2) {
+            game_area.draw_centered_text(
+                &format!("Cat Ball Wow! {}: {}", lang.get(Text::Score), scores.score),
+                97.0,
+                5.0,
+                text_color,
+            );
+            take_screenshot();
         }
-    }
-}
 
-impl Drawable for Scores {
-    fn draw(&self, game_area: &GameArea) {
-        let score_text_area =
-            game_area.game_to_screen(Vec2 { x: 5.0, y: 10.0 }, TranslateType::Normal);
-        let text_size =
-            game_area.game_to_screen(Vec2 { x: 10.0, y: 10.0 }, TranslateType::JustScale);
-        draw_text(
-            &format!("Score: {}", self.score),
-            score_text_area.x,
-            score_text_area.y,
-            text_size.x,
-            BLACK,
-        );
-        let best_score_text_area =
-            game_area.game_to_screen(Vec2 { x: 5.0, y: 17.5 }, TranslateType::Normal);
-        draw_text(
-            &format!("Best Score: {}", self.best_score),
-            best_score_text_area.x,
-            best_score_text_area.y,
-            text_size.x,
-            BLACK,
-        );
+        frame_limiter.wait();
+        next_frame().await
     }
 }
 
-#[macroquad::main("Cat Ball Wow!")]
-async fn main() {
-    // Load textures
-    let ball_texture: Texture2D = load_texture("assets/ball.png").await.unwrap();
-    ball_texture.set_filter(FilterMode::Linear);
-    let background_texture: Texture2D = load_texture("assets/background.png").await.unwrap();
-    background_texture.set_filter(FilterMode::Linear);
-    let left_paw_texture: Texture2D = load_texture("assets/paw_left.png").await.unwrap();
-    left_paw_texture.set_filter(FilterMode::Linear);
-    let right_paw_texture: Texture2D = load_texture("assets/paw_right.png").await.unwrap();
-    right_paw_texture.set_filter(FilterMode::Linear);
-
-    // Create game objects
-    let mut game_area = GameArea::new(background_texture);
-    let mut left_paw = Paw::new(left_paw_texture, PawSide::Left);
-    let mut right_paw = Paw::new(right_paw_texture, PawSide::Right);
-    let mut ball = Ball::new(ball_texture);
-    let mut scores = Scores::new();
-
-    loop {
-        clear_background(PINK);
-
-        game_area.update();
-        game_area.draw();
-
-        left_paw.update(&game_area);
-        right_paw.update(&game_area);
-        left_paw.draw(&game_area);
-        right_paw.draw(&game_area);
-
-        let paw_locations = vec![left_paw.rect, right_paw.rect];
-        ball.update(paw_locations, &mut scores);
-        ball.draw(&game_area);
-
-        scores.update();
-        scores.draw(&game_area);
-
-        next_frame().await
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balls_spawn_centered_on_any_playfield() {
+        // Never drawn, so any texture id will do and nothing needs a window
+        let texture = Texture2D::from_miniquad_texture(miniquad::TextureId::from_raw_id(
+            miniquad::RawId::OpenGl(0),
+        ));
+        let square = Config::default();
+        let wide = Config {
+            game_shape: Vec2 { x: 177.8, y: 100.0 },
+            ball_shape: Vec2 { x: 6.0, y: 6.0 },
+            ..Config::default()
+        };
+        for config in [square, wide] {
+            for chaos_mode in [false, true] {
+                for ball in new_balls(
+                    &texture,
+                    &config,
+                    Difficulty::Hard,
+                    Layout::PawsAtBottom,
+                    chaos_mode,
+                ) {
+                    let center = ball.rect().center();
+                    assert!(center.distance(config.game_shape / 2.0) < 0.001);
+                }
+            }
+        }
     }
 }
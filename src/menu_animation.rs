@@ -0,0 +1,126 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::game_area::{Drawable, GameArea, Sprite};
+use crate::input_state::InputState;
+use crate::layout::Layout;
+use crate::paw::{Paw, PawSide};
+use crate::response_curve::ResponseCurve;
+
+// Seconds the ball takes to hop from one paw to the other, arcing this many game units up
+const HOP_TIME: f32 = 1.2;
+const HOP_HEIGHT: f32 = 30.0;
+
+// The ball hopping back and forth between the paws behind the menu. It's scripted rather than
+// played out, with its own ball and paws that only borrow their drawing from the game's
+pub struct MenuAnimation {
+    ball_texture: Texture2D,
+    config: Config,
+    ball: Ball,
+    left_paw: Paw,
+    right_paw: Paw,
+    layout: Layout,
+    // Seconds spent on the menu, only counting while it's showing
+    time: f32,
+}
+
+impl MenuAnimation {
+    pub fn new(textures: [Texture2D; 3], config: &Config, layout: Layout) -> Self {
+        let [ball_texture, left_paw_texture, right_paw_texture] = textures;
+        let difficulty = Difficulty::Normal;
+        let new_paw = |texture, paw_side| {
+            Paw::new(
+                texture,
+                paw_side,
+                config,
+                difficulty,
+                layout,
+                ResponseCurve::Linear,
+            )
+        };
+        Self {
+            ball: Ball::new(ball_texture.clone(), config, difficulty, layout),
+            ball_texture,
+            config: *config,
+            left_paw: new_paw(left_paw_texture, PawSide::Left),
+            right_paw: new_paw(right_paw_texture, PawSide::Right),
+            layout,
+            time: 0.0,
+        }
+    }
+
+    // Each hop lands on the paw across from the one it left, flashing it like a real hit
+    pub fn update(&mut self) {
+        let delta = get_frame_time();
+        let previous_hop = (self.time / HOP_TIME) as u32;
+        self.time += delta;
+        let hop = (self.time / HOP_TIME) as u32;
+        let left_to_right = hop.is_multiple_of(2);
+        if hop != previous_hop {
+            if left_to_right {
+                self.left_paw.flash();
+            } else {
+                self.right_paw.flash();
+            }
+        }
+
+        let (from, to) = if left_to_right {
+            (&self.left_paw, &self.right_paw)
+        } else {
+            (&self.right_paw, &self.left_paw)
+        };
+        let (from_x, to_x) = (from.rect.center().x, to.rect.center().x);
+        let face_y = from.face_y();
+        let progress = (self.time / HOP_TIME).fract();
+        let height = HOP_HEIGHT * 4.0 * progress * (1.0 - progress);
+        let ball_size = self.ball.rect().size();
+        let center_x = from_x + (to_x - from_x) * progress;
+        let y = match self.layout {
+            Layout::PawsAtBottom => face_y - ball_size.y - height,
+            Layout::PawsAtTop => face_y + height,
+        };
+        // Only its direction matters, for which way the ball's drawn stretched
+        let velocity = Vec2 {
+            x: (to_x - from_x) / HOP_TIME,
+            y: HOP_HEIGHT * 4.0 * (2.0 * progress - 1.0) / HOP_TIME * self.layout.towards_paws(),
+        };
+        self.ball.place(
+            Vec2 {
+                x: center_x - ball_size.x / 2.0,
+                y,
+            },
+            velocity,
+        );
+
+        // Nothing steers them, they're only updated so their flashes fade
+        let input = InputState {
+            delta,
+            frame_time: delta,
+            ..Default::default()
+        };
+        self.left_paw.update(&input, &[]);
+        self.right_paw.update(&input, &[]);
+    }
+
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+        self.ball = Ball::new(
+            self.ball_texture.clone(),
+            &self.config,
+            Difficulty::Normal,
+            layout,
+        );
+        self.left_paw.set_layout(layout);
+        self.right_paw.set_layout(layout);
+    }
+}
+
+impl Drawable for MenuAnimation {
+    fn draw(&self, game_area: &GameArea) {
+        self.left_paw.draw(game_area);
+        self.right_paw.draw(game_area);
+        self.ball.draw(game_area);
+    }
+}
@@ -0,0 +1,26 @@
+use crate::storage::{load_value, save_value};
+
+const MIRROR_CONTROLS_KEY: &str = "mirror_controls";
+
+// Cross-handed touch controls, where each half of the screen steers the paw on the other half.
+// Off unless it's been turned on in settings
+pub struct MirrorControls {
+    enabled: bool,
+}
+
+impl MirrorControls {
+    pub fn new() -> Self {
+        Self {
+            enabled: load_value(MIRROR_CONTROLS_KEY).as_deref() == Some("true"),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(MIRROR_CONTROLS_KEY, &self.enabled.to_string());
+    }
+}
@@ -0,0 +1,83 @@
+use macroquad::prelude::*;
+
+use crate::game_area::{Drawable, GameArea, TranslateType};
+
+// A bar above where the ball launches from, so it's never in the way of the launch
+const CENTER_OBSTACLE: Rect = Rect {
+    x: 40.0,
+    y: 25.0,
+    w: 20.0,
+    h: 4.0,
+};
+// A block sliding side to side between the bar and the launch point, this many game units either
+// side of the middle at this many radians per second
+const MOVING_OBSTACLE: Rect = Rect {
+    x: 45.0,
+    y: 38.0,
+    w: 10.0,
+    h: 4.0,
+};
+const MOVING_OBSTACLE_AMPLITUDE: f32 = 35.0;
+const MOVING_OBSTACLE_SPEED: f32 = 1.2;
+const OBSTACLE_COLOR: Color = Color::new(0.35, 0.2, 0.1, 1.0);
+
+// Something in the playfield for the ball to bounce off
+pub struct Obstacle {
+    pub rect: Rect,
+    // Where the obstacle's left edge is in the middle of its path, and how far it goes either side
+    base_x: f32,
+    amplitude: f32,
+    // Radians per second, and how far along the sine path it is
+    speed: f32,
+    phase: f32,
+}
+
+impl Obstacle {
+    pub fn center() -> Self {
+        Self {
+            rect: CENTER_OBSTACLE,
+            base_x: CENTER_OBSTACLE.x,
+            amplitude: 0.0,
+            speed: 0.0,
+            phase: 0.0,
+        }
+    }
+
+    pub fn moving() -> Self {
+        Self {
+            rect: MOVING_OBSTACLE,
+            base_x: MOVING_OBSTACLE.x,
+            amplitude: MOVING_OBSTACLE_AMPLITUDE,
+            speed: MOVING_OBSTACLE_SPEED,
+            phase: 0.0,
+        }
+    }
+
+    // Moved so its path is centred across a playfield this wide, they're laid out for one 100
+    // game units wide
+    pub fn centered(mut self, game_width: f32) -> Self {
+        let offset = game_width / 2.0 - (self.base_x + self.rect.w / 2.0);
+        self.base_x += offset;
+        self.rect.x += offset;
+        self
+    }
+
+    // Turned upside down about the middle of the playfield, for when the paws are at the top
+    pub fn flipped(mut self, game_height: f32) -> Self {
+        self.rect.y = game_height - self.rect.y - self.rect.h;
+        self
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        self.phase = (self.phase + self.speed * delta) % std::f32::consts::TAU;
+        self.rect.x = self.base_x + self.amplitude * self.phase.sin();
+    }
+}
+
+impl Drawable for Obstacle {
+    fn draw(&self, game_area: &GameArea) {
+        let top_left = game_area.game_to_screen(self.rect.point(), TranslateType::Normal);
+        let size = game_area.game_to_screen(self.rect.size(), TranslateType::JustScale);
+        draw_rectangle(top_left.x, top_left.y, size.x, size.y, OBSTACLE_COLOR);
+    }
+}
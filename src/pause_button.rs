@@ -0,0 +1,39 @@
+use macroquad::prelude::*;
+
+use crate::game_area::{GameArea, TranslateType};
+
+// Next to the mute button, so touch players can pause without a keyboard
+pub const PAUSE_BUTTON: Rect = Rect {
+    x: 76.0,
+    y: 2.0,
+    w: 10.0,
+    h: 10.0,
+};
+
+// Two bars while playing, and a play triangle while paused
+pub fn draw_pause_button(game_area: &GameArea, paused: bool) {
+    let game_to_screen =
+        |x: f32, y: f32| game_area.game_to_screen(Vec2 { x, y }, TranslateType::Normal);
+    let button = PAUSE_BUTTON;
+
+    if paused {
+        draw_triangle(
+            game_to_screen(button.x + 2.5, button.y + 1.5),
+            game_to_screen(button.x + 2.5, button.y + 8.5),
+            game_to_screen(button.x + 8.5, button.y + 5.0),
+            game_area.theme.text(),
+        );
+    } else {
+        for x in [2.0, 6.0] {
+            let top_left = game_to_screen(button.x + x, button.y + 1.5);
+            let bottom_right = game_to_screen(button.x + x + 2.0, button.y + 8.5);
+            draw_rectangle(
+                top_left.x,
+                top_left.y,
+                bottom_right.x - top_left.x,
+                bottom_right.y - top_left.y,
+                game_area.theme.text(),
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+
+use crate::game_area::{Drawable, GameArea, TranslateType};
+use crate::layout::Layout;
+use crate::storage::{load_value, save_value};
+
+const PAUSE_GESTURE_KEY: &str = "pause_gesture";
+// A touch that strays further than this many game units from where it started is steering, not
+// part of a gesture
+const GESTURE_SLOP: f32 = 3.0;
+// Two fingers put down and lifted together within this many seconds are a two-finger tap
+const TWO_FINGER_TAP_TIME: f64 = 0.3;
+// A finger held still this many seconds on the hold spot is a long press
+const LONG_PRESS_TIME: f64 = 0.8;
+// The spot to hold for a long press, in the middle of the field this far in from the wall away
+// from the paws, clear of the buttons. Touches on it don't steer, since anywhere else a finger
+// held still is keeping its paw where it is
+const HOLD_SPOT_SIZE: Vec2 = Vec2 { x: 20.0, y: 10.0 };
+const HOLD_SPOT_MARGIN: f32 = 14.0;
+const HOLD_SPOT_ALPHA: f32 = 0.3;
+
+struct TrackedTouch {
+    start_position: Vec2,
+    start_time: f64,
+    // Whether it's strayed past the slop, after which it can't count towards a gesture
+    moved: bool,
+}
+
+// Pausing with touch gestures instead of the pause button, a two-finger tap or a long press on
+// the hold spot. On unless it's been turned off in settings
+pub struct PauseGesture {
+    enabled: bool,
+    touches: HashMap<u64, TrackedTouch>,
+    // When the last quick, still tap started and ended, waiting for a second finger's
+    last_tap: Option<(f64, f64)>,
+    // Set once a touch has paused the game, so holding it longer doesn't pause it again
+    long_pressed: Option<u64>,
+}
+
+impl PauseGesture {
+    pub fn new() -> Self {
+        Self {
+            enabled: load_value(PAUSE_GESTURE_KEY).as_deref() != Some("false"),
+            touches: HashMap::new(),
+            last_tap: None,
+            long_pressed: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(PAUSE_GESTURE_KEY, &self.enabled.to_string());
+    }
+
+    // Whether a touch at `game_point` is on the hold spot, and so kept from steering, which it
+    // only is while the gestures are on
+    pub fn claims(&self, game_point: Vec2, game_area: &GameArea) -> bool {
+        self.enabled && hold_spot(game_area.shape, game_area.layout).contains(game_point)
+    }
+
+    // Follows every touch each frame whatever the game is doing, and returns true on the frame a
+    // gesture asks to pause
+    pub fn update(&mut self, game_area: &GameArea) -> bool {
+        let now = get_time();
+        let mut paused = false;
+        for touch in touches() {
+            let position = game_area.screen_to_game(touch.position);
+            match touch.phase {
+                // Touches starting in the bars around the playfield aren't gestures
+                TouchPhase::Started if !game_area.contains_screen_point(touch.position) => {}
+                TouchPhase::Started => {
+                    self.touches.insert(
+                        touch.id,
+                        TrackedTouch {
+                            start_position: position,
+                            start_time: now,
+                            moved: false,
+                        },
+                    );
+                }
+                TouchPhase::Moved | TouchPhase::Stationary => {
+                    if let Some(tracked) = self.touches.get_mut(&touch.id) {
+                        tracked.moved |= tracked.start_position.distance(position) > GESTURE_SLOP;
+                    }
+                }
+                TouchPhase::Ended | TouchPhase::Cancelled => {
+                    if let Some(tracked) = self.touches.remove(&touch.id) {
+                        let quick = now - tracked.start_time <= TWO_FINGER_TAP_TIME;
+                        if quick && !tracked.moved {
+                            paused |= self.tapped(tracked.start_time, now);
+                        }
+                    }
+                    if self.long_pressed == Some(touch.id) {
+                        self.long_pressed = None;
+                    }
+                }
+            }
+        }
+
+        if self.long_pressed.is_none() {
+            let spot = hold_spot(game_area.shape, game_area.layout);
+            let long_press = self.touches.iter().find(|(_, tracked)| {
+                !tracked.moved
+                    && now - tracked.start_time >= LONG_PRESS_TIME
+                    && spot.contains(tracked.start_position)
+            });
+            if let Some((&id, _)) = long_press {
+                self.long_pressed = Some(id);
+                paused = true;
+            }
+        }
+
+        self.enabled && paused
+    }
+
+    // A quick still tap is half of a two-finger tap, and completes one if it was down at the same
+    // time as the last
+    fn tapped(&mut self, start_time: f64, end_time: f64) -> bool {
+        let overlapped = self.last_tap.is_some_and(|(last_start, last_end)| {
+            last_end >= start_time && end_time - last_start <= TWO_FINGER_TAP_TIME
+        });
+        self.last_tap = if overlapped {
+            None
+        } else {
+            Some((start_time, end_time))
+        };
+        overlapped
+    }
+}
+
+// Where the hold spot is on a playfield `game_shape` in size with the paws along `layout`'s edge
+fn hold_spot(game_shape: Vec2, layout: Layout) -> Rect {
+    let x = game_shape.x / 2.0 - HOLD_SPOT_SIZE.x / 2.0;
+    let y = match layout {
+        Layout::PawsAtBottom => HOLD_SPOT_MARGIN,
+        Layout::PawsAtTop => game_shape.y - HOLD_SPOT_MARGIN - HOLD_SPOT_SIZE.y,
+    };
+    Rect::new(x, y, HOLD_SPOT_SIZE.x, HOLD_SPOT_SIZE.y)
+}
+
+impl Drawable for PauseGesture {
+    // A faint outline with two pause bars in it, so there's something to find while it's on
+    fn draw(&self, game_area: &GameArea) {
+        if !self.enabled {
+            return;
+        }
+        let mut color = game_area.theme.text();
+        color.a = HOLD_SPOT_ALPHA;
+        let spot = hold_spot(game_area.shape, game_area.layout);
+        let line_thickness = game_area
+            .game_to_screen(Vec2 { x: 0.5, y: 0.5 }, TranslateType::JustScale)
+            .x;
+        let top_left = game_area.game_to_screen(spot.point(), TranslateType::Normal);
+        let size = game_area.game_to_screen(spot.size(), TranslateType::JustScale);
+        draw_rectangle_lines(
+            top_left.x,
+            top_left.y,
+            size.x,
+            size.y,
+            line_thickness,
+            color,
+        );
+        let center = spot.center();
+        for x in [-2.0, 1.0] {
+            let bar_top_left = game_area.game_to_screen(
+                Vec2 {
+                    x: center.x + x,
+                    y: center.y - 3.0,
+                },
+                TranslateType::Normal,
+            );
+            let bar_size =
+                game_area.game_to_screen(Vec2 { x: 1.0, y: 6.0 }, TranslateType::JustScale);
+            draw_rectangle(
+                bar_top_left.x,
+                bar_top_left.y,
+                bar_size.x,
+                bar_size.y,
+                color,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pause_button::PAUSE_BUTTON;
+    use crate::sounds::MUTE_BUTTON;
+
+    const GAME_SHAPES: [Vec2; 2] = [Vec2 { x: 100.0, y: 100.0 }, Vec2 { x: 177.8, y: 100.0 }];
+
+    #[test]
+    fn hold_spot_is_centered_on_the_far_side_from_the_paws() {
+        for game_shape in GAME_SHAPES {
+            let field = Rect::new(0.0, 0.0, game_shape.x, game_shape.y);
+            for (layout, far_side) in [(Layout::PawsAtBottom, 25.0), (Layout::PawsAtTop, 75.0)] {
+                let spot = hold_spot(game_shape, layout);
+                assert_eq!(spot.center().x, game_shape.x / 2.0);
+                assert!((spot.center().y - far_side).abs() < 10.0);
+                assert_eq!(spot.intersect(field), Some(spot));
+                for button in [MUTE_BUTTON, PAUSE_BUTTON] {
+                    assert!(!spot.overlaps(&button));
+                }
+            }
+        }
+    }
+}
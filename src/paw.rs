@@ -0,0 +1,671 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::ball_physics::PawBody;
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::game_area::{GameArea, Sprite, TranslateType};
+use crate::input_state::{steered_paw, InputState};
+use crate::layout::Layout;
+use crate::response_curve::ResponseCurve;
+use crate::theme::Theme;
+
+// Holding a key pushes the paw as hard as a finger a tenth of the game away would
+const PAW_KEYBOARD_ACCELERATION_SCALE: f32 = 0.1;
+// Weakest pull a touch has on its paw, as a fraction of the paw acceleration. The linear curve
+// pulls this hard two game units away, so nearer than that, down to the dead zone, this takes over
+const TOUCH_MIN_PULL: f32 = 0.02;
+// A touch this many game units or less from the paw's center doesn't pull it at all, so a
+// finger held roughly still over the paw doesn't make it twitch from side to side
+const TOUCH_DEAD_ZONE: f32 = 0.5;
+// The paw leans into its movement by this many radians per game unit per second, up to a limit
+const PAW_TILT: f32 = 0.002;
+const MAX_PAW_TILT: f32 = 0.25;
+// A power-up widens the paw by this much for a few seconds, growing and shrinking at a set speed
+const WIDE_PAW_SCALE: f32 = 1.6;
+const WIDE_PAW_DURATION: f32 = 6.0;
+const PAW_RESIZE_SPEED: f32 = 20.0;
+// Hitting the ball briefly swells the paw and warms its color, fading over this many seconds
+const HIT_FLASH_TIME: f32 = 0.2;
+const HIT_FLASH_SCALE: f32 = 0.12;
+const HIT_FLASH_COLOR: Color = Color::new(1.0, 0.75, 0.45, 1.0);
+// The computer pulls its paw like a touch would, only weaker, and only looks at where the ball
+// is every so often, so it can fall behind on quick bounces
+const AI_ACCELERATION_SCALE: f32 = 0.6;
+const AI_REACTION_TIME: f32 = 0.15;
+// Touching down twice on a paw's half within this many seconds dashes it towards the touch, as
+// fast as this impulse in game units per second, then it can't dash again for a while. The dash
+// leaves a few fading copies of the paw behind it for a moment
+const DOUBLE_TAP_TIME: f32 = 0.3;
+const DASH_SPEED: f32 = 120.0;
+const DASH_COOLDOWN: f32 = 1.5;
+const DASH_EFFECT_TIME: f32 = 0.25;
+const DASH_TRAIL_COPIES: usize = 3;
+const DASH_TRAIL_SPACING: f32 = 2.5;
+const DASH_TRAIL_ALPHA: f32 = 0.4;
+// While a dash is cooling down a thin bar across the paw's back edge fills back up, this fraction
+// of the paw's width and this many game units thick, inset this far from the edge
+const CHARGE_BAR_WIDTH: f32 = 0.6;
+const CHARGE_BAR_HEIGHT: f32 = 0.8;
+const CHARGE_BAR_INSET: f32 = 1.5;
+const CHARGE_BAR_ALPHA: f32 = 0.5;
+const CHARGE_TRACK_ALPHA: f32 = 0.2;
+const PAW_SHAPE: Vec2 = Vec2 {
+    x: 20.0 / 1.5,
+    y: 30.0 / 1.5,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PawSide {
+    Left,
+    Right,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PawController {
+    Human,
+    Ai,
+}
+
+impl PawSide {
+    // Keys that move the paw (left, right)
+    pub fn keys(&self) -> (KeyCode, KeyCode) {
+        match self {
+            PawSide::Left => (KeyCode::A, KeyCode::D),
+            PawSide::Right => (KeyCode::Left, KeyCode::Right),
+        }
+    }
+}
+
+pub struct Paw {
+    pub rect: Rect,
+    // The middle of the paw before the last physics step, to draw it part way from
+    previous_center_x: f32,
+    pub velocity: Vec2,
+    pub paw_side: PawSide,
+    pub controller: PawController,
+    difficulty: Difficulty,
+    // The paw's normal width, changing it eases the paw to the new width
+    pub width: f32,
+    layout: Layout,
+    // How much harder a touch pulls the further it is from the paw
+    response_curve: ResponseCurve,
+    // Whether it's steered by touches on the other half of the screen
+    mirrored: bool,
+    texture: Texture2D,
+    config: Config,
+    // Seconds left of being widened by a power-up
+    widen_timer: f32,
+    // Where the computer is steering to, and how long until it looks at the balls again
+    ai_target: f32,
+    ai_reaction_timer: f32,
+    // Seconds left of the flash from hitting the ball
+    hit_flash: f32,
+    // Whether its half was touched last frame, and seconds since a touch last came down on it
+    touching: bool,
+    since_touch_down: f32,
+    // Seconds until it can dash again, and left of the last dash's trail, drawn behind it
+    // opposite the dash's direction
+    dash_cooldown: f32,
+    dash_timer: f32,
+    dash_direction: f32,
+}
+
+impl Paw {
+    pub fn new(
+        texture: Texture2D,
+        paw_side: PawSide,
+        config: &Config,
+        difficulty: Difficulty,
+        layout: Layout,
+        response_curve: ResponseCurve,
+    ) -> Self {
+        let mut paw = Self {
+            rect: Rect {
+                h: PAW_SHAPE.y,
+                ..Default::default()
+            },
+            previous_center_x: 0.0,
+            velocity: Vec2::ZERO,
+            paw_side,
+            controller: PawController::Human,
+            difficulty,
+            width: PAW_SHAPE.x * difficulty.paw_scale(),
+            layout,
+            response_curve,
+            mirrored: false,
+            texture,
+            config: *config,
+            widen_timer: 0.0,
+            ai_target: 0.0,
+            ai_reaction_timer: 0.0,
+            hit_flash: 0.0,
+            touching: false,
+            since_touch_down: DOUBLE_TAP_TIME,
+            dash_cooldown: 0.0,
+            dash_timer: 0.0,
+            dash_direction: 0.0,
+        };
+        paw.reset();
+        paw
+    }
+
+    // Also sets the width the difficulty plays with
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = difficulty;
+        self.width = PAW_SHAPE.x * difficulty.paw_scale();
+    }
+
+    pub fn set_response_curve(&mut self, response_curve: ResponseCurve) {
+        self.response_curve = response_curve;
+    }
+
+    pub fn set_mirrored(&mut self, mirrored: bool) {
+        self.mirrored = mirrored;
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    // Moves the paw to the layout's edge, back at its starting spot
+    pub fn set_layout(&mut self, layout: Layout) {
+        self.layout = layout;
+        self.reset();
+    }
+
+    // Puts the paw back still at its starting spot, a quarter of the way in from its side along
+    // the layout's edge, at its normal width
+    pub fn reset(&mut self) {
+        let game_shape = self.config.game_shape;
+        self.rect.y = match self.layout {
+            Layout::PawsAtBottom => game_shape.y - self.rect.h,
+            Layout::PawsAtTop => 0.0,
+        };
+        self.rect.w = self.width.min(game_shape.x / 2.0);
+        self.rect.x = match self.paw_side {
+            PawSide::Left => game_shape.x / 4.0,
+            PawSide::Right => game_shape.x * 3.0 / 4.0 - self.rect.w,
+        };
+        let (min_x, max_x) = self.bounds();
+        self.rect.x = self.rect.x.clamp(min_x, max_x);
+        self.previous_center_x = self.rect.center().x;
+        self.velocity = Vec2::ZERO;
+        self.widen_timer = 0.0;
+        self.ai_reaction_timer = 0.0;
+        self.hit_flash = 0.0;
+        self.touching = false;
+        self.since_touch_down = DOUBLE_TAP_TIME;
+        self.dash_cooldown = 0.0;
+        self.dash_timer = 0.0;
+    }
+
+    pub fn widen(&mut self) {
+        self.widen_timer = WIDE_PAW_DURATION;
+    }
+
+    pub fn flash(&mut self) {
+        self.hit_flash = HIT_FLASH_TIME;
+    }
+
+    pub fn update(&mut self, input: &InputState, balls: &[Ball]) {
+        self.previous_center_x = self.rect.center().x;
+        let delta = input.delta;
+        let game_shape = self.config.game_shape;
+
+        self.dash_cooldown = (self.dash_cooldown - delta).max(0.0);
+        self.dash_timer = (self.dash_timer - delta).max(0.0);
+        let paw_acceleration = match self.controller {
+            PawController::Human => {
+                self.update_dash(input);
+                self.human_acceleration(input)
+            }
+            PawController::Ai => self.ai_acceleration(balls, delta),
+        };
+
+        // Friction is integrated exactly over the step, taking the push as steady through it, so
+        // the paw ends up in the same place however the time is split into steps
+        let drag = -self.config.paw_friction;
+        if drag != 0.0 {
+            let terminal_velocity = paw_acceleration / drag;
+            let decay = (-drag * delta).exp();
+            self.rect.x += terminal_velocity * delta
+                + (self.velocity.x - terminal_velocity) * (1.0 - decay) / drag;
+            self.velocity.x = terminal_velocity + (self.velocity.x - terminal_velocity) * decay;
+        } else {
+            self.rect.x += self.velocity.x * delta + 0.5 * paw_acceleration * delta * delta;
+            self.velocity.x += paw_acceleration * delta;
+        }
+
+        // Grow while widened and shrink back after, staying centered on the same spot
+        self.widen_timer = (self.widen_timer - delta).max(0.0);
+        self.hit_flash = (self.hit_flash - delta).max(0.0);
+        let target_width = if self.widen_timer > 0.0 {
+            self.width * WIDE_PAW_SCALE
+        } else {
+            self.width
+        };
+        let center_x = self.rect.center().x;
+        let resize_step = PAW_RESIZE_SPEED * delta;
+        self.rect.w += (target_width - self.rect.w).clamp(-resize_step, resize_step);
+        // A paw wider than its half would have nowhere to go, and would make the clamp below panic
+        self.rect.w = self.rect.w.min(game_shape.x / 2.0);
+        self.rect.x = center_x - self.rect.w / 2.0;
+
+        let (min_x, max_x) = self.bounds();
+        self.rect.x = self.rect.x.clamp(min_x, max_x);
+    }
+
+    // The edge of the paw the ball lands on
+    pub fn face_y(&self) -> f32 {
+        match self.layout {
+            Layout::PawsAtBottom => self.rect.y,
+            Layout::PawsAtTop => self.rect.bottom(),
+        }
+    }
+
+    // What the ball bounces off
+    pub fn body(&self) -> PawBody {
+        PawBody {
+            rect: self.rect,
+            velocity: self.velocity.x,
+            paw_side: self.paw_side,
+        }
+    }
+
+    // The middle of the paw's half
+    fn home_x(&self) -> f32 {
+        let game_shape = self.config.game_shape;
+        match self.paw_side {
+            PawSide::Left => game_shape.x / 4.0,
+            PawSide::Right => game_shape.x * 3.0 / 4.0,
+        }
+    }
+
+    // Keep the whole paw inside its own half whatever its current width, the left paw's right
+    // edge and the right paw's left edge both stop at the middle
+    fn bounds(&self) -> (f32, f32) {
+        let game_shape = self.config.game_shape;
+        match self.paw_side {
+            PawSide::Left => (0.0, game_shape.x / 2.0 - self.rect.w),
+            PawSide::Right => (game_shape.x / 2.0, game_shape.x - self.rect.w),
+        }
+    }
+
+    fn paw_acceleration(&self) -> f32 {
+        self.config.paw_acceleration * self.difficulty.paw_acceleration_scale()
+    }
+
+    // Where the touches that apply to this paw want it to go, picked between by the configured
+    // strategy. Mirrored, that's the ones on the other half, moved over onto this paw's half
+    fn touch_target(&self, input: &InputState) -> Option<f32> {
+        let game_shape = self.config.game_shape;
+        let half = game_shape.x / 2.0;
+        let shift = match (self.mirrored, self.paw_side) {
+            (false, _) => 0.0,
+            (true, PawSide::Left) => -half,
+            (true, PawSide::Right) => half,
+        };
+        let touches = input
+            .touches
+            .iter()
+            .filter(|touch| steered_paw(**touch, game_shape, self.mirrored) == Some(self.paw_side))
+            .map(|touch| Vec2 {
+                x: touch.x + shift,
+                ..*touch
+            });
+
+        self.config
+            .touch_strategy
+            .target_x(touches, self.rect.center().x)
+    }
+
+    // A second touch coming down soon after the first throws the paw towards it, if it's had
+    // long enough since its last dash
+    fn update_dash(&mut self, input: &InputState) {
+        let touch_x = self.touch_target(input);
+        self.since_touch_down += input.delta;
+        let touched_down = touch_x.is_some() && !self.touching;
+        self.touching = touch_x.is_some();
+        let Some(touch_x) = touch_x.filter(|_| touched_down) else {
+            return;
+        };
+        let offset = touch_x - self.rect.center().x;
+        if self.since_touch_down < DOUBLE_TAP_TIME
+            && self.dash_cooldown <= 0.0
+            && offset.abs() > TOUCH_DEAD_ZONE
+        {
+            self.dash_direction = offset.signum();
+            self.velocity.x += self.dash_direction * DASH_SPEED;
+            self.dash_cooldown = DASH_COOLDOWN;
+            self.dash_timer = DASH_EFFECT_TIME;
+            // A third tap straight after starts counting afresh rather than dashing again
+            self.since_touch_down = DOUBLE_TAP_TIME;
+        } else {
+            self.since_touch_down = 0.0;
+        }
+    }
+
+    // Acceleration from touches on this paw's half or tilting, its keys and its gamepad stick
+    fn human_acceleration(&self, input: &InputState) -> f32 {
+        let game_shape = self.config.game_shape;
+        let paw_acceleration_factor = self.paw_acceleration();
+
+        // Apply acceleration towards the touches
+        let touch_x = self.touch_target(input);
+        // Without a touch, tilting steers the paw across its half as if a finger was there
+        let target_x = touch_x.or_else(|| {
+            input
+                .tilt
+                .map(|steering| self.home_x() + steering * game_shape.x / 4.0)
+        });
+        let mut paw_acceleration = target_x.map_or(0.0, |target_x| {
+            touch_acceleration(
+                self.response_curve,
+                target_x - self.rect.center().x,
+                paw_acceleration_factor,
+                game_shape.x,
+            )
+        });
+
+        // Keys and sticks work alongside touches
+        paw_acceleration += input.steering(self.paw_side)
+            * paw_acceleration_factor
+            * PAW_KEYBOARD_ACCELERATION_SCALE;
+        paw_acceleration
+    }
+
+    // The computer steers towards the lowest ball falling on its half as if a finger was
+    // following it, and back to the middle of its half when there isn't one
+    fn ai_acceleration(&mut self, balls: &[Ball], delta: f32) -> f32 {
+        let game_shape = self.config.game_shape;
+
+        self.ai_reaction_timer -= delta;
+        if self.ai_reaction_timer <= 0.0 {
+            self.ai_reaction_timer = AI_REACTION_TIME;
+            let on_this_half = |x: f32| match self.paw_side {
+                PawSide::Left => x < game_shape.x / 2.0,
+                PawSide::Right => x >= game_shape.x / 2.0,
+            };
+            let home = self.home_x();
+            let towards_paws = self.layout.towards_paws();
+            self.ai_target = balls
+                .iter()
+                .map(|ball| (ball.rect(), ball.physics.velocity))
+                .filter(|(rect, velocity)| {
+                    velocity.y * towards_paws > 0.0 && on_this_half(rect.center().x)
+                })
+                .max_by(|a, b| (a.0.y * towards_paws).total_cmp(&(b.0.y * towards_paws)))
+                .map_or(home, |(rect, _)| rect.center().x);
+        }
+
+        // The computer's tuned to the linear curve, whichever the player's chosen
+        touch_acceleration(
+            ResponseCurve::Linear,
+            self.ai_target - self.rect.center().x,
+            self.paw_acceleration() * AI_ACCELERATION_SCALE,
+            game_shape.x,
+        )
+    }
+
+    // Hidden once the paw can dash again
+    fn draw_charge_bar(&self, game_area: &GameArea, paw_rect: Rect) {
+        if self.dash_cooldown <= 0.0 {
+            return;
+        }
+        let width = paw_rect.w * CHARGE_BAR_WIDTH;
+        let track = Rect {
+            x: paw_rect.center().x - width / 2.0,
+            y: match self.layout {
+                Layout::PawsAtBottom => paw_rect.bottom() - CHARGE_BAR_INSET - CHARGE_BAR_HEIGHT,
+                Layout::PawsAtTop => paw_rect.y + CHARGE_BAR_INSET,
+            },
+            w: width,
+            h: CHARGE_BAR_HEIGHT,
+        };
+        let charge = 1.0 - self.dash_cooldown / DASH_COOLDOWN;
+        let top_left = game_area.game_to_screen(track.point(), TranslateType::Normal);
+        let size = game_area.game_to_screen(track.size(), TranslateType::JustScale);
+        let color = game_area.theme.text();
+        draw_rectangle(
+            top_left.x,
+            top_left.y,
+            size.x,
+            size.y,
+            Color {
+                a: CHARGE_TRACK_ALPHA,
+                ..color
+            },
+        );
+        draw_rectangle(
+            top_left.x,
+            top_left.y,
+            size.x * charge,
+            size.y,
+            Color {
+                a: CHARGE_BAR_ALPHA,
+                ..color
+            },
+        );
+    }
+
+    // `alpha` is how far from where it was before the last physics step to where it is now it's
+    // drawn at, from 0 to 1. Swells from the middle of its edge while flashing, so it stays sat
+    // on the edge, and trails copies of itself just after a dash
+    pub fn draw_interpolated(&self, game_area: &GameArea, alpha: f32) {
+        let center_x =
+            self.previous_center_x + (self.rect.center().x - self.previous_center_x) * alpha;
+        let paw_rect = Rect {
+            x: center_x - self.rect.w / 2.0,
+            ..self.rect
+        };
+        let scale = 1.0 + HIT_FLASH_SCALE * self.hit_flash / HIT_FLASH_TIME;
+        let size = paw_rect.size() * scale;
+        let rect = Rect {
+            x: paw_rect.center().x - size.x / 2.0,
+            y: match self.layout {
+                Layout::PawsAtBottom => paw_rect.bottom() - size.y,
+                Layout::PawsAtTop => paw_rect.y,
+            },
+            w: size.x,
+            h: size.y,
+        };
+        let tint = self.tint(game_area.theme);
+        let dash_fade = self.dash_timer / DASH_EFFECT_TIME;
+        if dash_fade > 0.0 {
+            for copy in 1..=DASH_TRAIL_COPIES {
+                let trail = Rect {
+                    x: rect.x - self.dash_direction * DASH_TRAIL_SPACING * copy as f32,
+                    ..rect
+                };
+                let color = Color {
+                    a: DASH_TRAIL_ALPHA * dash_fade / copy as f32,
+                    ..tint
+                };
+                self.draw_at(game_area, trail, color);
+            }
+        }
+        self.draw_at(game_area, rect, tint);
+        self.draw_charge_bar(game_area, paw_rect);
+    }
+}
+
+// How hard a touch `offset` game units from the paw's center pulls on it. The pull is a spring,
+// growing with the distance along the chosen response curve (linear is the old
+// `PAW_ACCELERATION / (1.0 / (distance / width))` simplified), so far touches yank the paw over
+// and near ones ease it in. It never drops below TOUCH_MIN_PULL of the full force so a finger
+// just off-center still reels the paw in, except inside TOUCH_DEAD_ZONE where it lets go and
+// the paw's friction settles it
+fn touch_acceleration(
+    response_curve: ResponseCurve,
+    offset: f32,
+    paw_acceleration: f32,
+    game_width: f32,
+) -> f32 {
+    if offset.abs() <= TOUCH_DEAD_ZONE {
+        return 0.0;
+    }
+    (response_curve
+        .pull(offset.abs() / game_width)
+        .max(TOUCH_MIN_PULL)
+        * paw_acceleration)
+        .copysign(offset)
+}
+
+impl Sprite for Paw {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    fn tint(&self, theme: Theme) -> Color {
+        let flash = self.hit_flash / HIT_FLASH_TIME;
+        let tint = theme.paw_tint().to_vec();
+        Color::from_vec(tint.lerp(HIT_FLASH_COLOR.to_vec(), flash))
+    }
+
+    // Upside down when along the top
+    fn rotation(&self) -> f32 {
+        let tilt = (self.velocity.x * PAW_TILT).clamp(-MAX_PAW_TILT, MAX_PAW_TILT);
+        match self.layout {
+            Layout::PawsAtBottom => tilt,
+            Layout::PawsAtTop => tilt + std::f32::consts::PI,
+        }
+    }
+
+    // Where the last physics step left it
+    fn draw(&self, game_area: &GameArea) {
+        self.draw_interpolated(game_area, 1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Never drawn, so any texture id will do and nothing needs a window
+    fn paw(paw_side: PawSide) -> Paw {
+        let texture = Texture2D::from_miniquad_texture(miniquad::TextureId::from_raw_id(
+            miniquad::RawId::OpenGl(0),
+        ));
+        Paw::new(
+            texture,
+            paw_side,
+            &Config::default(),
+            Difficulty::Normal,
+            Layout::PawsAtBottom,
+            ResponseCurve::Linear,
+        )
+    }
+
+    // Holding the paw's key towards the right
+    fn steering_right(delta: f32) -> InputState {
+        InputState {
+            left_steering: 1.0,
+            delta,
+            frame_time: delta,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn paw_ends_up_in_the_same_place_at_any_frame_rate() {
+        let step = 1.0 / 120.0;
+        let mut once = paw(PawSide::Left);
+        let mut twice = paw(PawSide::Left);
+        for _ in 0..6 {
+            once.update(&steering_right(2.0 * step), &[]);
+        }
+        for _ in 0..12 {
+            twice.update(&steering_right(step), &[]);
+        }
+        assert!(once.rect.x > paw(PawSide::Left).rect.x);
+        assert!((once.rect.x - twice.rect.x).abs() < 0.01);
+    }
+
+    #[test]
+    fn touch_pull_grows_with_distance_and_lets_go_in_the_dead_zone() {
+        let curves = [
+            ResponseCurve::Linear,
+            ResponseCurve::Smooth,
+            ResponseCurve::Snappy,
+        ];
+        for curve in curves {
+            let pull = |offset| touch_acceleration(curve, offset, 1000.0, 100.0);
+            assert_eq!(pull(0.0), 0.0);
+            assert_eq!(pull(TOUCH_DEAD_ZONE), 0.0);
+            let mut last = 0.0;
+            for tenth in 6..=1000 {
+                let offset = tenth as f32 / 10.0;
+                assert!(pull(offset) >= TOUCH_MIN_PULL * 1000.0);
+                assert!(pull(offset) >= last, "weaker {} away", offset);
+                assert_eq!(pull(-offset), -pull(offset));
+                last = pull(offset);
+            }
+        }
+    }
+
+    #[test]
+    fn touch_just_off_center_still_reels_the_paw_in() {
+        // The linear curve alone would barely pull a finger a game unit away
+        let pull = touch_acceleration(ResponseCurve::Linear, 1.0, 1000.0, 100.0);
+        assert_eq!(pull, TOUCH_MIN_PULL * 1000.0);
+        assert!(pull > ResponseCurve::Linear.pull(0.01) * 1000.0);
+        // And the floor gives way to the curve once it pulls harder
+        let far = touch_acceleration(ResponseCurve::Linear, 30.0, 1000.0, 100.0);
+        assert_eq!(far, ResponseCurve::Linear.pull(0.3) * 1000.0);
+    }
+
+    // Shoves the paw as hard as it'll go towards one side for a while
+    fn push(paw: &mut Paw, towards: f32) {
+        for _ in 0..60 {
+            paw.velocity.x = towards * 1000.0;
+            paw.update(
+                &InputState {
+                    delta: 1.0 / 60.0,
+                    ..Default::default()
+                },
+                &[],
+            );
+        }
+    }
+
+    #[test]
+    fn paw_stays_in_its_own_half_at_any_width() {
+        let scales = Difficulty::ALL.map(|difficulty| difficulty.paw_scale());
+        let narrowest = PAW_SHAPE.x * scales.into_iter().fold(f32::INFINITY, f32::min);
+        let widest = PAW_SHAPE.x * scales.into_iter().fold(0.0, f32::max);
+        for paw_side in [PawSide::Left, PawSide::Right] {
+            let (half_left, half_right) = match paw_side {
+                PawSide::Left => (0.0, 50.0),
+                PawSide::Right => (50.0, 100.0),
+            };
+            // Widened by a power-up at the widest, and set wider than the half itself
+            for (width, widened) in [(narrowest, false), (widest, true), (80.0, false)] {
+                let mut paw = paw(paw_side);
+                paw.width = width;
+                paw.reset();
+                if widened {
+                    paw.widen();
+                }
+                for towards in [-1.0, 1.0] {
+                    push(&mut paw, towards);
+                    assert!(paw.rect.left() >= half_left, "{} wide", width);
+                    assert!(paw.rect.right() <= half_right + 0.001, "{} wide", width);
+                    let edge = if towards < 0.0 {
+                        paw.rect.left() - half_left
+                    } else {
+                        half_right - paw.rect.right()
+                    };
+                    assert!(edge.abs() < 0.001, "{} wide, not against the edge", width);
+                }
+                if widened {
+                    assert!((paw.rect.w - width * WIDE_PAW_SCALE).abs() < 0.001);
+                }
+            }
+        }
+    }
+}
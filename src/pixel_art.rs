@@ -0,0 +1,46 @@
+use macroquad::prelude::*;
+
+use crate::storage::{load_value, save_value};
+
+const PIXEL_ART_KEY: &str = "pixel_art";
+
+// Draws the textures with nearest-neighbor filtering so they scale up crisp and blocky instead of
+// smoothed. The textures are kept so the filter can be switched while the game runs. Off unless
+// it's been turned on in settings
+pub struct PixelArt {
+    enabled: bool,
+    textures: Vec<Texture2D>,
+}
+
+impl PixelArt {
+    pub fn new(textures: Vec<Texture2D>) -> Self {
+        let pixel_art = Self {
+            enabled: load_value(PIXEL_ART_KEY).as_deref() == Some("true"),
+            textures,
+        };
+        pixel_art.apply();
+        pixel_art
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(PIXEL_ART_KEY, &self.enabled.to_string());
+        self.apply();
+    }
+
+    // Every copy of a texture shares the one on the GPU, so this changes it wherever it's drawn
+    fn apply(&self) {
+        let filter = if self.enabled {
+            FilterMode::Nearest
+        } else {
+            FilterMode::Linear
+        };
+        for texture in &self.textures {
+            texture.set_filter(filter);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::config::Config;
+use crate::game_area::{GameArea, Sprite};
+use crate::paw::PawSide;
+
+const POWER_UP_SIZE: f32 = 8.0;
+// Seconds between one power-up going away and the next appearing
+const MIN_SPAWN_DELAY: f32 = 8.0;
+const MAX_SPAWN_DELAY: f32 = 15.0;
+// Seconds a power-up waits to be collected before disappearing
+const POWER_UP_LIFETIME: f32 = 6.0;
+// Power-ups appear between these heights, clear of the paws
+const MIN_SPAWN_Y: f32 = 20.0;
+const MAX_SPAWN_Y: f32 = 55.0;
+
+// A collectible that widens a paw when the ball passes through it
+pub struct PowerUp {
+    rect: Rect,
+    texture: Texture2D,
+    // Seconds left before the power-up disappears, zero while there isn't one out
+    active_timer: f32,
+    // Seconds until the next power-up appears
+    spawn_timer: f32,
+    config: Config,
+}
+
+impl PowerUp {
+    pub fn new(texture: Texture2D, config: &Config) -> Self {
+        let mut power_up = Self {
+            rect: Rect {
+                w: POWER_UP_SIZE,
+                h: POWER_UP_SIZE,
+                ..Default::default()
+            },
+            texture,
+            active_timer: 0.0,
+            spawn_timer: 0.0,
+            config: *config,
+        };
+        power_up.reset();
+        power_up
+    }
+
+    // Returns the side of the paw that collected the power-up, if a ball went through it. Its
+    // timers run in real time, so slow motion doesn't keep it out any longer
+    pub fn update(&mut self, balls: &[Ball], delta: f32) -> Option<PawSide> {
+        let game_shape = self.config.game_shape;
+
+        if self.active_timer <= 0.0 {
+            self.spawn_timer -= delta;
+            if self.spawn_timer <= 0.0 {
+                self.rect.x = rand::gen_range(0.0, game_shape.x - self.rect.w);
+                self.rect.y = rand::gen_range(MIN_SPAWN_Y, MAX_SPAWN_Y);
+                self.active_timer = POWER_UP_LIFETIME;
+            }
+            return None;
+        }
+
+        self.active_timer -= delta;
+        if self.active_timer <= 0.0 {
+            self.reset();
+            return None;
+        }
+
+        let ball = balls.iter().find(|ball| ball.rect().overlaps(&self.rect))?;
+        self.reset();
+        // The paw that sent the ball through gets it, or the paw on that half if none has yet
+        Some(
+            ball.physics
+                .last_paw
+                .unwrap_or(if self.rect.center().x < game_shape.x / 2.0 {
+                    PawSide::Left
+                } else {
+                    PawSide::Right
+                }),
+        )
+    }
+
+    // Takes away any power-up that's out and waits to spawn the next one
+    pub fn reset(&mut self) {
+        self.active_timer = 0.0;
+        self.spawn_timer = rand::gen_range(MIN_SPAWN_DELAY, MAX_SPAWN_DELAY);
+    }
+}
+
+impl Sprite for PowerUp {
+    fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    fn draw(&self, game_area: &GameArea) {
+        if self.active_timer > 0.0 {
+            self.draw_at(game_area, self.rect, self.tint(game_area.theme));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use macroquad::prelude::*;
+
+use crate::ball::Ball;
+use crate::config::Config;
+use crate::difficulty::Difficulty;
+use crate::game_area::{GameArea, Sprite};
+use crate::layout::Layout;
+use crate::scores::ScoreMode;
+use crate::storage::{load_document, save_document};
+use crate::theme::Theme;
+
+// The ball's position is recorded this often, in seconds of game time
+const SAMPLE_INTERVAL: f32 = 0.05;
+// Positions are saved as whole tenths of a game unit to keep the saved replay small
+const SAVE_PRECISION: f32 = 10.0;
+const GHOST_ALPHA: f32 = 0.3;
+// Seconds the ghost takes to fade away once the run it's from is over
+const GHOST_FADE_TIME: f32 = 1.0;
+
+// Records the ball's path through each run, and plays back the best one as a faint ghost ball
+// for the player to race
+pub struct Replay {
+    texture: Texture2D,
+    config: Config,
+    difficulty: Difficulty,
+    mode: ScoreMode,
+    layout: Layout,
+    // The first ball's position every sample this run
+    recording: Vec<Vec2>,
+    // The best run's positions, and the result the mode judges it by
+    ghost: Vec<Vec2>,
+    ghost_score: u32,
+    // Seconds of game time into this run
+    time: f32,
+}
+
+impl Replay {
+    // Each difficulty and mode has its own ghost, like its own best, and so does each layout since
+    // a ghost from the other one would fly upside down. Practice and zen have none, there's no
+    // best run in them to race
+    pub fn new(
+        texture: Texture2D,
+        config: &Config,
+        difficulty: Difficulty,
+        mode: ScoreMode,
+        layout: Layout,
+    ) -> Self {
+        let mut replay = Self {
+            texture,
+            config: *config,
+            difficulty,
+            mode,
+            layout,
+            recording: Vec::new(),
+            ghost: Vec::new(),
+            ghost_score: 0,
+            time: 0.0,
+        };
+        replay.load();
+        replay
+    }
+
+    pub fn update(&mut self, balls: &[Ball], delta: f32) {
+        self.time += delta;
+        let Some(ball) = balls.first() else {
+            return;
+        };
+        while self.recording.len() as f32 * SAMPLE_INTERVAL <= self.time {
+            self.recording.push(ball.rect().point());
+        }
+    }
+
+    // Keeps this run as the ghost if it beat the one before. `result` is what the mode is judged
+    // by, the bounces or the seconds survived for survival
+    pub fn end_run(&mut self, result: u32) {
+        if self.races() && result > self.ghost_score {
+            self.ghost = std::mem::take(&mut self.recording);
+            self.ghost_score = result;
+            self.save();
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.recording.clear();
+        self.time = 0.0;
+    }
+
+    // Where the ghost is now, between the two samples either side, or where its run ended
+    fn ghost_point(&self) -> Option<Vec2> {
+        let sample = self.time / SAMPLE_INTERVAL;
+        let index = sample as usize;
+        match (self.ghost.get(index), self.ghost.get(index + 1)) {
+            (Some(from), Some(to)) => Some(from.lerp(*to, sample.fract())),
+            _ => self.ghost.last().copied(),
+        }
+    }
+
+    // Fades from fully shown to gone over the fade time after the ghost's run ends
+    fn fade(&self) -> f32 {
+        let ghost_duration = self.ghost.len().saturating_sub(1) as f32 * SAMPLE_INTERVAL;
+        (1.0 - (self.time - ghost_duration) / GHOST_FADE_TIME).clamp(0.0, 1.0)
+    }
+
+    fn races(&self) -> bool {
+        matches!(self.mode, ScoreMode::Bounces | ScoreMode::Survival)
+    }
+
+    fn document_name(&self) -> String {
+        format!(
+            "ghost_{}_{}_{}.txt",
+            self.difficulty.key(),
+            self.mode.key(),
+            self.layout.key()
+        )
+    }
+
+    // Saved as the result on the first line, then every position as x,y pairs
+    fn load(&mut self) {
+        if !self.races() {
+            return;
+        }
+        let Some(contents) = load_document(&self.document_name()) else {
+            return;
+        };
+        let mut lines = contents.lines();
+        let Some(ghost_score) = lines.next().and_then(|line| line.parse().ok()) else {
+            return;
+        };
+        // A corrupt position means a corrupt replay, so it's thrown away altogether
+        let ghost: Option<Vec<Vec2>> = lines
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|point| {
+                let (x, y) = point.split_once(',')?;
+                Some(Vec2 {
+                    x: x.parse::<i32>().ok()? as f32 / SAVE_PRECISION,
+                    y: y.parse::<i32>().ok()? as f32 / SAVE_PRECISION,
+                })
+            })
+            .collect();
+        if let Some(ghost) = ghost {
+            self.ghost = ghost;
+            self.ghost_score = ghost_score;
+        }
+    }
+
+    fn save(&self) {
+        let points: Vec<String> = self
+            .ghost
+            .iter()
+            .map(|point| {
+                format!(
+                    "{},{}",
+                    (point.x * SAVE_PRECISION).round() as i32,
+                    (point.y * SAVE_PRECISION).round() as i32
+                )
+            })
+            .collect();
+        save_document(
+            &self.document_name(),
+            &format!("{}\n{}\n", self.ghost_score, points.join(" ")),
+        );
+    }
+}
+
+impl Sprite for Replay {
+    fn rect(&self) -> Rect {
+        let point = self.ghost_point().unwrap_or_default();
+        Rect {
+            x: point.x,
+            y: point.y,
+            w: self.config.ball_shape.x * self.difficulty.ball_scale(),
+            h: self.config.ball_shape.y * self.difficulty.ball_scale(),
+        }
+    }
+
+    fn texture(&self) -> &Texture2D {
+        &self.texture
+    }
+
+    fn tint(&self, theme: Theme) -> Color {
+        let mut color = theme.ball_tint();
+        color.a = GHOST_ALPHA * self.fade();
+        color
+    }
+
+    // Nothing to show without a ghost, or before the run starts
+    fn draw(&self, game_area: &GameArea) {
+        if !self.ghost.is_empty() && self.time > 0.0 {
+            self.draw_at(game_area, self.rect(), self.tint(game_area.theme));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Nothing loaded from or saved to settings. Never drawn, so any texture id will do
+    fn replay(mode: ScoreMode, layout: Layout) -> Replay {
+        Replay {
+            texture: Texture2D::from_miniquad_texture(miniquad::TextureId::from_raw_id(
+                miniquad::RawId::OpenGl(0),
+            )),
+            config: Config::default(),
+            difficulty: Difficulty::Normal,
+            mode,
+            layout,
+            recording: vec![Vec2::ZERO; 10],
+            ghost: Vec::new(),
+            ghost_score: 0,
+            time: 0.0,
+        }
+    }
+
+    #[test]
+    fn practice_and_zen_runs_never_become_ghosts() {
+        for mode in [ScoreMode::Practice, ScoreMode::Zen] {
+            let mut replay = replay(mode, Layout::PawsAtBottom);
+            replay.end_run(100);
+            assert!(replay.ghost.is_empty());
+        }
+    }
+
+    #[test]
+    fn each_mode_and_layout_keeps_its_own_ghost() {
+        let names: Vec<String> = [ScoreMode::Bounces, ScoreMode::Survival]
+            .into_iter()
+            .flat_map(|mode| [Layout::PawsAtBottom, Layout::PawsAtTop].map(|layout| (mode, layout)))
+            .map(|(mode, layout)| replay(mode, layout).document_name())
+            .collect();
+        for (index, name) in names.iter().enumerate() {
+            assert!(!names[index + 1..].contains(name), "{name}");
+        }
+    }
+}
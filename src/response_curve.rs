@@ -0,0 +1,115 @@
+use crate::lang::Text;
+use crate::storage::{load_value, save_value};
+
+const RESPONSE_CURVE_KEY: &str = "response_curve";
+// The smooth curve pulls as hard as the linear one for a touch this fraction of the game away,
+// softer nearer and harder further
+const SMOOTH_MATCH_DISTANCE: f32 = 0.25;
+// The snappy curve reaches its full pull by this fraction of the game away, and pulls no harder
+// beyond it
+const SNAP_DISTANCE: f32 = 0.05;
+const SNAP_PULL: f32 = 0.25;
+// A touch on a paw's own half can't be further than half the game from it, so every curve is at
+// its strongest by then
+const SATURATION_DISTANCE: f32 = 0.5;
+
+// How a touch's pull on its paw grows with its distance from it, from smooth to twitchy
+#[derive(Clone, Copy, PartialEq)]
+pub enum ResponseCurve {
+    Linear,
+    Smooth,
+    Snappy,
+}
+
+impl ResponseCurve {
+    const ALL: [ResponseCurve; 3] = [
+        ResponseCurve::Linear,
+        ResponseCurve::Smooth,
+        ResponseCurve::Snappy,
+    ];
+
+    // The last chosen curve, or linear if there isn't a saved one
+    pub fn load() -> Self {
+        let saved = load_value(RESPONSE_CURVE_KEY);
+        Self::ALL
+            .into_iter()
+            .find(|curve| saved.as_deref() == Some(curve.key()))
+            .unwrap_or(ResponseCurve::Linear)
+    }
+
+    pub fn save(&self) {
+        save_value(RESPONSE_CURVE_KEY, self.key());
+    }
+
+    // Name used for this in saved values
+    pub fn key(&self) -> &'static str {
+        match self {
+            ResponseCurve::Linear => "linear",
+            ResponseCurve::Smooth => "smooth",
+            ResponseCurve::Snappy => "snappy",
+        }
+    }
+
+    pub fn label(&self) -> Text {
+        match self {
+            ResponseCurve::Linear => Text::Linear,
+            ResponseCurve::Smooth => Text::Smooth,
+            ResponseCurve::Snappy => Text::Snappy,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ResponseCurve::Linear => ResponseCurve::Smooth,
+            ResponseCurve::Smooth => ResponseCurve::Snappy,
+            ResponseCurve::Snappy => ResponseCurve::Linear,
+        }
+    }
+
+    // The pull, as a fraction of the paw acceleration, of a touch `distance` away as a fraction
+    // of the game's width. Never lessens as the distance grows
+    pub fn pull(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0.0, SATURATION_DISTANCE);
+        match self {
+            ResponseCurve::Linear => distance,
+            ResponseCurve::Smooth => distance * distance / SMOOTH_MATCH_DISTANCE,
+            ResponseCurve::Snappy => (distance / SNAP_DISTANCE).min(1.0) * SNAP_PULL,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_curve_is_zero_at_zero() {
+        for curve in ResponseCurve::ALL {
+            assert_eq!(curve.pull(0.0), 0.0, "{}", curve.key());
+        }
+    }
+
+    #[test]
+    fn every_curve_pulls_harder_the_further_away() {
+        for curve in ResponseCurve::ALL {
+            let mut last = curve.pull(0.0);
+            for hundredth in 1..=50 {
+                let pull = curve.pull(hundredth as f32 / 100.0);
+                assert!(pull >= last, "{} weaker at {}", curve.key(), hundredth);
+                last = pull;
+            }
+            // Strictly harder from one end of the half to the other
+            assert!(curve.pull(SATURATION_DISTANCE) > curve.pull(0.01));
+        }
+    }
+
+    #[test]
+    fn every_curve_saturates_at_the_half_width() {
+        for curve in ResponseCurve::ALL {
+            let strongest = curve.pull(SATURATION_DISTANCE);
+            for distance in [0.6, 0.75, 1.0, 10.0] {
+                assert_eq!(curve.pull(distance), strongest, "{}", curve.key());
+            }
+        }
+    }
+}
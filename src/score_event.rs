@@ -0,0 +1,37 @@
+// What the game reports as scoring happens, so a page embedding it can react without reading
+// anything off the screen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScoreEvent {
+    // The new score, including going back to zero for a new run
+    ScoreChanged(u32),
+    // The new combo after a paw hit bumped it up
+    ComboIncreased(u32),
+    // The run is over, with its final score
+    GameOver(u32),
+}
+
+pub type ScoreHook = Box<dyn FnMut(ScoreEvent)>;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_score_event(kind: u32, value: u32);
+}
+
+// On the web every event is passed on to the page as a cat_ball_wow_score DOM event, elsewhere
+// there's nothing to pass them on to
+#[cfg(target_arch = "wasm32")]
+pub fn page_hook() -> Option<ScoreHook> {
+    Some(Box::new(|event| {
+        let (kind, value) = match event {
+            ScoreEvent::ScoreChanged(score) => (0, score),
+            ScoreEvent::ComboIncreased(combo) => (1, combo),
+            ScoreEvent::GameOver(score) => (2, score),
+        };
+        unsafe { cat_ball_wow_score_event(kind, value) };
+    }))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn page_hook() -> Option<ScoreHook> {
+    None
+}
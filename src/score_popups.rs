@@ -0,0 +1,108 @@
+use macroquad::prelude::*;
+
+use crate::game_area::{Drawable, GameArea, TranslateType};
+use crate::lang::Text;
+
+// Seconds a popup lasts, fading out as it floats up at this many game units per second
+const POPUP_LIFETIME: f32 = 0.5;
+const POPUP_RISE_SPEED: f32 = 12.0;
+const POPUP_FONT_SIZE: f32 = 6.0;
+// A lost combo is shown bigger and for longer, in the danger color
+const COMBO_BREAK_LIFETIME: f32 = 1.0;
+const COMBO_BREAK_FONT_SIZE: f32 = 8.0;
+
+enum PopupKind {
+    // Points scored
+    Points(u32),
+    // The combo that was lost
+    ComboBreak(u32),
+}
+
+struct Popup {
+    // Center of the text in game units
+    position: Vec2,
+    kind: PopupKind,
+    age: f32,
+}
+
+impl Popup {
+    fn lifetime(&self) -> f32 {
+        match self.kind {
+            PopupKind::Points(_) => POPUP_LIFETIME,
+            PopupKind::ComboBreak(_) => COMBO_BREAK_LIFETIME,
+        }
+    }
+}
+
+// "+N" texts floating up from wherever points were scored
+pub struct ScorePopups {
+    popups: Vec<Popup>,
+}
+
+impl ScorePopups {
+    pub fn new() -> Self {
+        Self { popups: Vec::new() }
+    }
+
+    pub fn spawn(&mut self, position: Vec2, points: u32) {
+        self.popups.push(Popup {
+            position,
+            kind: PopupKind::Points(points),
+            age: 0.0,
+        });
+    }
+
+    pub fn spawn_combo_break(&mut self, position: Vec2, combo: u32) {
+        self.popups.push(Popup {
+            position,
+            kind: PopupKind::ComboBreak(combo),
+            age: 0.0,
+        });
+    }
+
+    pub fn update(&mut self) {
+        let delta = get_frame_time();
+        for popup in self.popups.iter_mut() {
+            popup.age += delta;
+            popup.position.y -= POPUP_RISE_SPEED * delta;
+        }
+        self.popups.retain(|popup| popup.age < popup.lifetime());
+    }
+
+    pub fn reset(&mut self) {
+        self.popups.clear();
+    }
+}
+
+impl Drawable for ScorePopups {
+    fn draw(&self, game_area: &GameArea) {
+        let scale = game_area
+            .game_to_screen(Vec2 { x: 1.0, y: 1.0 }, TranslateType::JustScale)
+            .x;
+        for popup in &self.popups {
+            let (text, font_size, color) = match popup.kind {
+                PopupKind::Points(points) => (
+                    format!("+{}", points),
+                    POPUP_FONT_SIZE,
+                    game_area.theme.text(),
+                ),
+                PopupKind::ComboBreak(combo) => (
+                    format!("{} x{}", game_area.lang.get(Text::ComboLost), combo),
+                    COMBO_BREAK_FONT_SIZE,
+                    game_area.theme.danger(),
+                ),
+            };
+            let font_size = font_size * scale;
+            let text_dimensions = measure_text(&text, None, font_size as u16, 1.0);
+            let position = game_area.game_to_screen(popup.position, TranslateType::Normal);
+            let alpha = 1.0 - popup.age / popup.lifetime();
+            draw_text(
+                &text,
+                position.x - text_dimensions.width / 2.0,
+                position.y,
+                font_size,
+                Color { a: alpha, ..color },
+            );
+        }
+    }
+}
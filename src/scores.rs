@@ -0,0 +1,604 @@
+use std::collections::HashMap;
+
+use macroquad::prelude::*;
+
+use crate::difficulty::Difficulty;
+use crate::game_area::{Drawable, GameArea, TranslateType};
+use crate::lang::Text;
+use crate::layout::Layout;
+use crate::score_event::{ScoreEvent, ScoreHook};
+use crate::storage::{load_value, save_value};
+use crate::theme::Theme;
+
+// Each difficulty's best is saved under this followed by the difficulty's key. Saves from before
+// difficulties existed have a single best under just this, which counts as Normal's
+const BEST_SCORE_KEY: &str = "best_score";
+// Best survival times are saved the same way, under this followed by the difficulty's key
+const BEST_TIME_KEY: &str = "best_time";
+// Zen runs are slower, so they keep bests of their own under this
+const BEST_ZEN_SCORE_KEY: &str = "best_zen_score";
+const SCORE_MODE_KEY: &str = "score_mode";
+// The shadow is offset down and right by this fraction of the font size
+const SHADOW_OFFSET: f32 = 0.06;
+// The score and best lines are never drawn smaller than this many pixels, however small the window
+const MIN_FONT_SIZE: f32 = 14.0;
+// With the paws along the top the score moves down this far, out from under them
+const PAWS_AT_TOP_OFFSET: f32 = 62.0;
+// The score sits on a see-through panel so it can be read over anything, sized in game units and
+// with the text this far in from its left edge
+const SCORE_PANEL: Rect = Rect {
+    x: 2.0,
+    y: 2.0,
+    w: 60.0,
+    h: 29.0,
+};
+const PANEL_PADDING: f32 = 3.0;
+const PANEL_CORNER_RADIUS: f32 = 2.0;
+const PANEL_CORNER_SEGMENTS: u32 = 6;
+const PANEL_ALPHA: f32 = 0.35;
+// Beating the record mid-run shows a banner for this many seconds, popping in this much bigger
+const NEW_BEST_TIME: f32 = 1.5;
+const NEW_BEST_POP: f32 = 0.3;
+// Safety nets are kept up to this many at once, each drawn as a strand this many game units
+// thick along the paws' edge, this far apart
+const MAX_SAFETY_NETS: u32 = 3;
+const SAFETY_NET_THICKNESS: f32 = 0.5;
+const SAFETY_NET_SPACING: f32 = 1.2;
+// Remaining lives are drawn as a row of dots under the score
+const LIFE_ICON_RADIUS: f32 = 1.2;
+const LIFE_ICON_SPACING: f32 = 3.5;
+
+// What a run is judged by, bounces off the walls or how long the ball is kept up. Practice
+// counts bounces too, but never ends, keeps the ball at its starting speed and sets no records.
+// Zen is for unwinding, with a slow steady ball, softer colors and sound, and the score kept
+// out of sight until the run's over
+#[derive(Clone, Copy, PartialEq)]
+pub enum ScoreMode {
+    Bounces,
+    Survival,
+    Practice,
+    Zen,
+}
+
+impl ScoreMode {
+    // The last chosen mode, or bounces if there isn't a saved one
+    pub fn load() -> Self {
+        match load_value(SCORE_MODE_KEY).as_deref() {
+            Some("survival") => ScoreMode::Survival,
+            Some("practice") => ScoreMode::Practice,
+            Some("zen") => ScoreMode::Zen,
+            _ => ScoreMode::Bounces,
+        }
+    }
+
+    pub fn save(&self) {
+        save_value(SCORE_MODE_KEY, self.key());
+    }
+
+    // Name used for this mode in saved values
+    pub fn key(&self) -> &'static str {
+        match self {
+            ScoreMode::Bounces => "bounces",
+            ScoreMode::Survival => "survival",
+            ScoreMode::Practice => "practice",
+            ScoreMode::Zen => "zen",
+        }
+    }
+
+    pub fn label(&self) -> Text {
+        match self {
+            ScoreMode::Bounces => Text::Bounces,
+            ScoreMode::Survival => Text::Survival,
+            ScoreMode::Practice => Text::Practice,
+            ScoreMode::Zen => Text::Zen,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ScoreMode::Bounces => ScoreMode::Survival,
+            ScoreMode::Survival => ScoreMode::Practice,
+            ScoreMode::Practice => ScoreMode::Zen,
+            ScoreMode::Zen => ScoreMode::Bounces,
+        }
+    }
+}
+
+pub struct Scores {
+    pub score: u32,
+    // Best for each difficulty, ones without a saved best are left out
+    best_scores: HashMap<Difficulty, u32>,
+    // The difficulty being played, whose best is shown and beaten
+    pub difficulty: Difficulty,
+    pub mode: ScoreMode,
+    // Seconds the ball has been in play this run
+    pub survival_time: f32,
+    // Longest survival time for each difficulty, like the best scores
+    best_times: HashMap<Difficulty, f32>,
+    // Best zen score for each difficulty, kept apart from the others
+    best_zen_scores: HashMap<Difficulty, u32>,
+    // Paw hits this run, to show which paw carried the rally
+    pub left_hits: u32,
+    pub right_hits: u32,
+    // Every point is multiplied by this, and each paw hit without losing the ball bumps it up
+    pub combo: u32,
+    // Balls that can still be lost before the game is over
+    pub lives: u32,
+    // Times a ball falling off the paws' edge will be bounced back instead, earned with combos
+    pub safety_nets: u32,
+    // Whether this run has beaten the record it started with, so it's only celebrated once, and
+    // seconds left of the banner saying so
+    beat_best: bool,
+    new_best_timer: f32,
+    // Drawn with macroquad's default font when this is missing
+    font: Option<Font>,
+    // Told about every change to the score and combo, and the end of each run
+    event_hook: Option<ScoreHook>,
+}
+
+impl Scores {
+    pub fn new(difficulty: Difficulty, font: Option<Font>) -> Self {
+        let mut scores = Self {
+            score: 0,
+            best_scores: HashMap::new(),
+            difficulty,
+            mode: ScoreMode::load(),
+            survival_time: 0.0,
+            best_times: HashMap::new(),
+            best_zen_scores: HashMap::new(),
+            left_hits: 0,
+            right_hits: 0,
+            combo: 1,
+            lives: difficulty.lives(),
+            safety_nets: 0,
+            beat_best: false,
+            new_best_timer: 0.0,
+            font,
+            event_hook: None,
+        };
+        scores.load();
+        scores
+    }
+
+    pub fn set_event_hook(&mut self, hook: ScoreHook) {
+        self.event_hook = Some(hook);
+    }
+
+    fn emit(&mut self, event: ScoreEvent) {
+        if let Some(hook) = &mut self.event_hook {
+            hook(event);
+        }
+    }
+
+    // Scores a bounce, worth the current combo
+    pub fn add_points(&mut self) {
+        self.score += self.combo;
+        self.emit(ScoreEvent::ScoreChanged(self.score));
+    }
+
+    // Reaching every `safety_net_combo` in a combo earns a safety net
+    pub fn bump_combo(&mut self, safety_net_combo: u32) {
+        self.combo += 1;
+        self.emit(ScoreEvent::ComboIncreased(self.combo));
+        if safety_net_combo > 0 && self.combo.is_multiple_of(safety_net_combo) {
+            self.safety_nets = (self.safety_nets + 1).min(MAX_SAFETY_NETS);
+        }
+    }
+
+    // Uses up a safety net if there is one
+    pub fn catch_ball(&mut self) -> bool {
+        if self.safety_nets == 0 {
+            return false;
+        }
+        self.safety_nets -= 1;
+        true
+    }
+
+    // The clock only runs once the balls have launched
+    pub fn update(&mut self, ball_in_play: bool, frame_time: f32) {
+        self.new_best_timer = (self.new_best_timer - frame_time).max(0.0);
+        match self.mode {
+            ScoreMode::Practice => return,
+            // Quietly kept, there's no banner to break the calm
+            ScoreMode::Zen => {
+                if self.score > self.best_zen_score() {
+                    self.best_zen_scores.insert(self.difficulty, self.score);
+                    save_value(
+                        &best_key(BEST_ZEN_SCORE_KEY, self.difficulty),
+                        &self.score.to_string(),
+                    );
+                }
+                return;
+            }
+            ScoreMode::Bounces | ScoreMode::Survival => {}
+        }
+        let best_score = self.best_score();
+        // Each mode only celebrates the record it's judged by
+        if self.score > best_score {
+            if self.mode == ScoreMode::Bounces {
+                self.celebrate_new_best(best_score > 0);
+            }
+            self.best_scores.insert(self.difficulty, self.score);
+            self.save();
+        }
+        // The time is survival's own record, so the clock only runs there
+        if ball_in_play && self.mode == ScoreMode::Survival {
+            self.survival_time += frame_time;
+            let best_time = self.best_time();
+            if self.survival_time > best_time {
+                self.celebrate_new_best(best_time > 0.0);
+                self.best_times.insert(self.difficulty, self.survival_time);
+            }
+        }
+    }
+
+    // Shows the banner the first time the record's beaten in a run, unless there wasn't a
+    // record to beat
+    fn celebrate_new_best(&mut self, had_best: bool) {
+        if !self.beat_best && had_best {
+            self.new_best_timer = NEW_BEST_TIME;
+        }
+        self.beat_best = true;
+    }
+
+    // The time goes up every frame, so its best is only saved once the run is over
+    pub fn end_run(&mut self) {
+        self.emit(ScoreEvent::GameOver(self.score));
+        self.new_best_timer = 0.0;
+        if self.mode == ScoreMode::Survival && self.survival_time >= self.best_time() {
+            save_value(
+                &best_key(BEST_TIME_KEY, self.difficulty),
+                &self.best_time().to_string(),
+            );
+        }
+    }
+
+    pub fn best_score(&self) -> u32 {
+        self.best_scores.get(&self.difficulty).copied().unwrap_or(0)
+    }
+
+    pub fn best_zen_score(&self) -> u32 {
+        self.best_zen_scores
+            .get(&self.difficulty)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn best_time(&self) -> f32 {
+        self.best_times
+            .get(&self.difficulty)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    fn load(&mut self) {
+        // A missing or corrupt save just means starting from zero
+        for difficulty in Difficulty::ALL {
+            let mut value = load_value(&best_key(BEST_SCORE_KEY, difficulty));
+            if value.is_none() && difficulty == Difficulty::Normal {
+                value = load_value(BEST_SCORE_KEY);
+            }
+            if let Some(best_score) = value.and_then(|value| value.parse().ok()) {
+                self.best_scores.insert(difficulty, best_score);
+            }
+            let best_time = load_value(&best_key(BEST_TIME_KEY, difficulty))
+                .and_then(|value| value.parse().ok());
+            if let Some(best_time) = best_time {
+                self.best_times.insert(difficulty, best_time);
+            }
+            let best_zen_score = load_value(&best_key(BEST_ZEN_SCORE_KEY, difficulty))
+                .and_then(|value| value.parse().ok());
+            if let Some(best_zen_score) = best_zen_score {
+                self.best_zen_scores.insert(difficulty, best_zen_score);
+            }
+        }
+    }
+
+    fn save(&self) {
+        save_value(
+            &best_key(BEST_SCORE_KEY, self.difficulty),
+            &self.best_score().to_string(),
+        );
+    }
+
+    pub fn reset(&mut self) {
+        if self.score != 0 {
+            self.score = 0;
+            self.emit(ScoreEvent::ScoreChanged(0));
+        }
+        self.survival_time = 0.0;
+        self.left_hits = 0;
+        self.right_hits = 0;
+        self.combo = 1;
+        self.lives = self.difficulty.lives();
+        self.safety_nets = 0;
+        self.beat_best = false;
+        self.new_best_timer = 0.0;
+    }
+
+    // Draws the text over a shadow of itself so it can be read on any part of the background
+    fn draw_text(&self, text: &str, position: Vec2, font_size: f32, theme: Theme) {
+        let shadow_offset = font_size * SHADOW_OFFSET;
+        for (offset, color) in [(shadow_offset, theme.text_shadow()), (0.0, theme.text())] {
+            draw_text_ex(
+                text,
+                position.x + offset,
+                position.y + offset,
+                TextParams {
+                    font: self.font.as_ref(),
+                    font_size: font_size as u16,
+                    color,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    // A strand along the paws' edge for each safety net, stacked in from the edge
+    pub fn draw_safety_nets(&self, game_area: &GameArea) {
+        let color = game_area.theme.safety_net();
+        for net in 0..self.safety_nets {
+            let inset = SAFETY_NET_SPACING * net as f32;
+            let y = match game_area.layout {
+                Layout::PawsAtBottom => game_area.shape.y - inset - SAFETY_NET_THICKNESS,
+                Layout::PawsAtTop => inset,
+            };
+            let top_left = game_area.game_to_screen(Vec2 { x: 0.0, y }, TranslateType::Normal);
+            let size = game_area.game_to_screen(
+                Vec2 {
+                    x: game_area.shape.x,
+                    y: SAFETY_NET_THICKNESS,
+                },
+                TranslateType::JustScale,
+            );
+            draw_rectangle(top_left.x, top_left.y, size.x, size.y, color);
+        }
+    }
+}
+
+fn best_key(key: &str, difficulty: Difficulty) -> String {
+    format!("{}_{}", key, difficulty.key())
+}
+
+// Whole seconds as MM:SS
+pub fn format_duration(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl Drawable for Scores {
+    // Nothing's shown in zen, the game over screen has the score once the run's done
+    fn draw(&self, game_area: &GameArea) {
+        if self.mode == ScoreMode::Zen {
+            return;
+        }
+        let top = match game_area.layout {
+            Layout::PawsAtBottom => 0.0,
+            Layout::PawsAtTop => PAWS_AT_TOP_OFFSET,
+        };
+        let panel = Rect {
+            y: SCORE_PANEL.y + top,
+            ..SCORE_PANEL
+        };
+        // Every line is placed this far in from the panel's top left corner, in game units
+        let line_position = |x: f32, y: f32| {
+            game_area.game_to_screen(
+                Vec2 {
+                    x: panel.x + x,
+                    y: panel.y + y,
+                },
+                TranslateType::Normal,
+            )
+        };
+        let text_size =
+            game_area.game_to_screen(Vec2 { x: 10.0, y: 10.0 }, TranslateType::JustScale);
+        let font_size = text_size.x.max(MIN_FONT_SIZE);
+        let hits_font_size = text_size.x * 0.7;
+        // Lines below get pushed down to make room when the floor makes the text bigger
+        let floor_growth = font_size - text_size.x;
+        let lang = game_area.lang;
+        let bounces_text = if self.combo > 1 {
+            format!(
+                "{}: {} (x{})",
+                lang.get(Text::Score),
+                self.score,
+                self.combo
+            )
+        } else {
+            format!("{}: {}", lang.get(Text::Score), self.score)
+        };
+        let (score_text, best_score_text) = match self.mode {
+            ScoreMode::Bounces | ScoreMode::Zen => (
+                bounces_text,
+                format!(
+                    "{} ({}): {}",
+                    lang.get(Text::BestScore),
+                    lang.get(self.difficulty.label()),
+                    self.best_score()
+                ),
+            ),
+            // There's no best to show, so say why not
+            ScoreMode::Practice => (
+                bounces_text,
+                format!(
+                    "{}: {}",
+                    lang.get(Text::Mode),
+                    lang.get(ScoreMode::Practice.label())
+                ),
+            ),
+            ScoreMode::Survival => (
+                format!(
+                    "{}: {}",
+                    lang.get(Text::Time),
+                    format_duration(self.survival_time)
+                ),
+                format!(
+                    "{} ({}): {}",
+                    lang.get(Text::BestTime),
+                    lang.get(self.difficulty.label()),
+                    format_duration(self.best_time())
+                ),
+            ),
+        };
+        let hits_text = format!(
+            "{}: {}  {}: {}",
+            lang.get(Text::LeftPaw),
+            self.left_hits,
+            lang.get(Text::RightPaw),
+            self.right_hits
+        );
+
+        // The panel is drawn first, stretched to fit the longest line if the text outgrows it
+        let measure = |text: &str, font_size: f32| {
+            measure_text(text, self.font.as_ref(), font_size as u16, 1.0).width
+        };
+        let text_width = measure(&score_text, font_size)
+            .max(measure(&best_score_text, font_size))
+            .max(measure(&hits_text, hits_font_size));
+        let panel_top_left = line_position(0.0, 0.0);
+        let panel_size = game_area.game_to_screen(panel.size(), TranslateType::JustScale);
+        let padding = line_position(PANEL_PADDING, 0.0).x - panel_top_left.x;
+        let corner_radius = game_area
+            .game_to_screen(
+                Vec2 {
+                    x: PANEL_CORNER_RADIUS,
+                    y: PANEL_CORNER_RADIUS,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        draw_rounded_rectangle(
+            Rect {
+                x: panel_top_left.x,
+                y: panel_top_left.y,
+                w: panel_size.x.max(text_width + padding * 2.0),
+                h: panel_size.y + floor_growth,
+            },
+            corner_radius,
+            game_area.theme.overlay(PANEL_ALPHA),
+        );
+
+        self.draw_text(
+            &score_text,
+            line_position(PANEL_PADDING, 8.0),
+            font_size,
+            game_area.theme,
+        );
+        self.draw_text(
+            &best_score_text,
+            line_position(PANEL_PADDING, 15.5) + Vec2::Y * floor_growth * 0.75,
+            font_size,
+            game_area.theme,
+        );
+        self.draw_text(
+            &hits_text,
+            line_position(PANEL_PADDING, 22.0) + Vec2::Y * floor_growth,
+            hits_font_size,
+            game_area.theme,
+        );
+        let radius = game_area
+            .game_to_screen(
+                Vec2 {
+                    x: LIFE_ICON_RADIUS,
+                    y: LIFE_ICON_RADIUS,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        // Shadowed like the text. Practice has no lives to lose
+        let lives = if self.mode == ScoreMode::Practice {
+            0
+        } else {
+            self.lives
+        };
+        for life in 0..lives {
+            let center = line_position(PANEL_PADDING + 1.5 + life as f32 * LIFE_ICON_SPACING, 26.5)
+                + Vec2::Y * floor_growth;
+            let shadow_offset = radius * 0.25;
+            draw_circle(
+                center.x + shadow_offset,
+                center.y + shadow_offset,
+                radius,
+                game_area.theme.text_shadow(),
+            );
+            draw_circle(center.x, center.y, radius, game_area.theme.text());
+        }
+
+        // Pops in big and shrinks back as it fades
+        if self.new_best_timer > 0.0 {
+            let progress = self.new_best_timer / NEW_BEST_TIME;
+            let mut color = game_area.theme.text();
+            color.a = progress.min(0.5) * 2.0;
+            game_area.draw_centered_text(
+                lang.get(Text::NewBest),
+                45.0,
+                12.0 * (1.0 + NEW_BEST_POP * progress * progress),
+                color,
+            );
+        }
+    }
+}
+
+// Fanned out from the middle as triangles that don't overlap, so a see-through color is just as
+// see-through everywhere, corners included
+fn draw_rounded_rectangle(rect: Rect, radius: f32, color: Color) {
+    let radius = radius.min(rect.w / 2.0).min(rect.h / 2.0);
+    let corners = [
+        (vec2(rect.right() - radius, rect.bottom() - radius), 0.0),
+        (vec2(rect.x + radius, rect.bottom() - radius), 0.5),
+        (vec2(rect.x + radius, rect.y + radius), 1.0),
+        (vec2(rect.right() - radius, rect.y + radius), 1.5),
+    ];
+    let outline: Vec<Vec2> = corners
+        .iter()
+        .flat_map(|&(center, start)| {
+            (0..=PANEL_CORNER_SEGMENTS).map(move |segment| {
+                let angle = (start + segment as f32 / PANEL_CORNER_SEGMENTS as f32 * 0.5)
+                    * std::f32::consts::PI;
+                center + Vec2::from_angle(angle) * radius
+            })
+        })
+        .collect();
+    let middle = rect.center();
+    for (index, point) in outline.iter().enumerate() {
+        draw_triangle(middle, *point, outline[(index + 1) % outline.len()], color);
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    // Nothing loaded from or saved to settings
+    pub fn scores(mode: ScoreMode) -> Scores {
+        Scores {
+            score: 0,
+            best_scores: HashMap::new(),
+            difficulty: Difficulty::Normal,
+            mode,
+            survival_time: 0.0,
+            best_times: HashMap::new(),
+            best_zen_scores: HashMap::new(),
+            left_hits: 0,
+            right_hits: 0,
+            combo: 1,
+            lives: Difficulty::Normal.lives(),
+            safety_nets: 0,
+            beat_best: false,
+            new_best_timer: 0.0,
+            font: None,
+            event_hook: None,
+        }
+    }
+
+    #[test]
+    fn a_bounces_run_leaves_the_best_time_alone() {
+        let mut scores = scores(ScoreMode::Bounces);
+        for _ in 0..600 {
+            scores.update(true, 1.0 / 60.0);
+        }
+        scores.end_run();
+        assert_eq!(scores.survival_time, 0.0);
+        assert_eq!(scores.best_time(), 0.0);
+    }
+}
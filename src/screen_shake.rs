@@ -0,0 +1,51 @@
+use macroquad::prelude::*;
+
+use crate::game_area::{GameArea, TranslateType};
+
+// Largest shake in game units, small enough that the playfield always stays on screen
+const MAX_SHAKE: f32 = 2.0;
+// How quickly a shake dies down, per second
+const SHAKE_DECAY: f32 = 10.0;
+
+pub struct ScreenShake {
+    // Current shake in game units
+    intensity: f32,
+    // State of the shake's own random numbers. Taking them from the game's would change how a
+    // recorded run plays out depending on when the screen shook
+    noise: u32,
+}
+
+impl ScreenShake {
+    pub fn new() -> Self {
+        Self {
+            intensity: 0.0,
+            noise: miniquad::date::now() as u32 | 1,
+        }
+    }
+
+    // A new shake only takes over if it's stronger than what's left of the current one
+    pub fn shake(&mut self, intensity: f32) {
+        self.intensity = self.intensity.max(intensity).min(MAX_SHAKE);
+    }
+
+    pub fn update(&mut self) {
+        self.intensity *= (-SHAKE_DECAY * get_frame_time()).exp();
+    }
+
+    // Random offset in screen units to move the playfield by this frame
+    pub fn offset(&mut self, game_area: &GameArea) -> Vec2 {
+        let game_offset = Vec2 {
+            x: self.next_noise(),
+            y: self.next_noise(),
+        } * self.intensity;
+        game_area.game_to_screen(game_offset, TranslateType::JustScale)
+    }
+
+    // A xorshift step, from -1 to 1
+    fn next_noise(&mut self) -> f32 {
+        self.noise ^= self.noise << 13;
+        self.noise ^= self.noise >> 17;
+        self.noise ^= self.noise << 5;
+        self.noise as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+}
@@ -0,0 +1,57 @@
+use image::codecs::png::PngEncoder;
+use image::{ColorType, ImageEncoder};
+use macroquad::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use sapp_jsutils::{JsObject, JsObjectWeak};
+
+use crate::date::{format_date, format_time};
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_download(name: JsObjectWeak, data: JsObjectWeak);
+}
+
+// Saves what's been drawn so far this frame as a PNG, to the working directory on desktop and as
+// a download on the web. Call it after drawing and before next_frame
+pub fn take_screenshot() {
+    let image = get_screen_data();
+    let width = image.width as usize;
+    let height = image.height as usize;
+
+    // The screen is read bottom row first
+    let mut bytes = Vec::with_capacity(image.bytes.len());
+    for row in image.bytes.chunks_exact(width * 4).rev() {
+        bytes.extend_from_slice(row);
+    }
+
+    let mut png = Vec::new();
+    if let Err(error) =
+        PngEncoder::new(&mut png).write_image(&bytes, width as u32, height as u32, ColorType::Rgba8)
+    {
+        warn!("Could not encode the screenshot: {}", error);
+        return;
+    }
+
+    let timestamp = miniquad::date::now() as u64;
+    let name = format!(
+        "cat_ball_wow_{}_{}.png",
+        format_date(timestamp),
+        format_time(timestamp)
+    );
+    save_png(&name, &png);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_png(name: &str, png: &[u8]) {
+    match std::fs::write(name, png) {
+        Ok(()) => info!("Saved a screenshot to {}", name),
+        Err(error) => warn!("Could not save the screenshot to {}: {}", name, error),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_png(name: &str, png: &[u8]) {
+    let name = JsObject::string(name);
+    let data = JsObject::buffer(png);
+    unsafe { cat_ball_wow_download(name.weak(), data.weak()) };
+}
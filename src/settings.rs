@@ -0,0 +1,521 @@
+use macroquad::prelude::*;
+
+use crate::frame_limiter::{FrameLimiter, FPS_CAP};
+use crate::game_area::{GameArea, TranslateType};
+use crate::haptics::Haptics;
+use crate::landing_marker::LandingMarker;
+use crate::lang::Text;
+use crate::mirror_controls::MirrorControls;
+use crate::pause_gesture::PauseGesture;
+use crate::pixel_art::PixelArt;
+use crate::response_curve::ResponseCurve;
+use crate::sounds::Sounds;
+use crate::steady_speed::SteadySpeed;
+use crate::tilt::Tilt;
+
+// Slider tracks in game units, their handles can be grabbed a little above and below them
+const MUSIC_SLIDER: Rect = Rect {
+    x: 20.0,
+    y: 18.0,
+    w: 60.0,
+    h: 2.0,
+};
+const EFFECTS_SLIDER: Rect = Rect {
+    x: 20.0,
+    y: 26.0,
+    w: 60.0,
+    h: 2.0,
+};
+const SLIDER_GRAB_MARGIN: f32 = 3.0;
+const SLIDER_HANDLE_RADIUS: f32 = 2.5;
+// Rows on the general page
+const MUTE_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 31.0,
+    w: 60.0,
+    h: 6.0,
+};
+const THEME_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 37.0,
+    w: 60.0,
+    h: 6.0,
+};
+const LANG_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 43.0,
+    w: 60.0,
+    h: 6.0,
+};
+const LAYOUT_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 49.0,
+    w: 60.0,
+    h: 6.0,
+};
+const FPS_CAP_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 55.0,
+    w: 60.0,
+    h: 6.0,
+};
+// The frame rate cap is only shown on desktop and vibration only on the web, so they share a row
+const HAPTICS_TOGGLE: Rect = FPS_CAP_TOGGLE;
+const PIXEL_ART_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 61.0,
+    w: 60.0,
+    h: 6.0,
+};
+// Rows on the controls page
+const RESPONSE_CURVE_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 31.0,
+    w: 60.0,
+    h: 6.0,
+};
+const MIRROR_CONTROLS_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 37.0,
+    w: 60.0,
+    h: 6.0,
+};
+const PAUSE_GESTURE_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 43.0,
+    w: 60.0,
+    h: 6.0,
+};
+// Tilt steering is only on the web, and calibrating only while it's on
+const TILT_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 49.0,
+    w: 60.0,
+    h: 6.0,
+};
+const TILT_CALIBRATE_BUTTON: Rect = Rect {
+    x: 20.0,
+    y: 55.0,
+    w: 60.0,
+    h: 6.0,
+};
+// Rows on the accessibility page
+const STEADY_SPEED_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 31.0,
+    w: 60.0,
+    h: 6.0,
+};
+const LANDING_MARKER_TOGGLE: Rect = Rect {
+    x: 20.0,
+    y: 37.0,
+    w: 60.0,
+    h: 6.0,
+};
+// On every page, moving on to the next one and going back to the menu
+const PAGE_BUTTON: Rect = Rect {
+    x: 20.0,
+    y: 83.0,
+    w: 60.0,
+    h: 6.0,
+};
+const BACK_BUTTON: Rect = Rect {
+    x: 20.0,
+    y: 91.0,
+    w: 60.0,
+    h: 8.0,
+};
+
+#[derive(Clone, Copy)]
+enum Slider {
+    Music,
+    Effects,
+}
+
+impl Slider {
+    fn track(&self) -> Rect {
+        match self {
+            Slider::Music => MUSIC_SLIDER,
+            Slider::Effects => EFFECTS_SLIDER,
+        }
+    }
+
+    fn grab_area(&self) -> Rect {
+        let track = self.track();
+        Rect {
+            x: track.x - SLIDER_GRAB_MARGIN,
+            y: track.y - SLIDER_GRAB_MARGIN,
+            w: track.w + SLIDER_GRAB_MARGIN * 2.0,
+            h: track.h + SLIDER_GRAB_MARGIN * 2.0,
+        }
+    }
+
+    fn value(&self, sounds: &Sounds) -> f32 {
+        match self {
+            Slider::Music => sounds.music_volume(),
+            Slider::Effects => sounds.effects_volume(),
+        }
+    }
+}
+
+// Settings are split over pages, with the ones for how the paws are steered and the ones making
+// the game easier to play on their own
+#[derive(Clone, Copy, PartialEq)]
+enum Page {
+    General,
+    Controls,
+    Accessibility,
+}
+
+impl Page {
+    fn title(&self) -> Text {
+        match self {
+            Page::General => Text::Settings,
+            Page::Controls => Text::Controls,
+            Page::Accessibility => Text::Accessibility,
+        }
+    }
+
+    fn next(&self) -> Self {
+        match self {
+            Page::General => Page::Controls,
+            Page::Controls => Page::Accessibility,
+            Page::Accessibility => Page::General,
+        }
+    }
+}
+
+// Volume sliders, mute, theme and language toggles and the platform's own settings, reached from
+// the menu, with the steering and accessibility settings on pages of their own
+pub struct Settings {
+    // The slider being dragged, if any
+    dragging: Option<Slider>,
+    page: Page,
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Self {
+            dragging: None,
+            page: Page::General,
+        }
+    }
+
+    // Returns true once the player wants to go back to the menu
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        game_area: &mut GameArea,
+        sounds: &mut Sounds,
+        frame_limiter: &mut FrameLimiter,
+        haptics: &mut Haptics,
+        tilt: &mut Tilt,
+        landing_marker: &mut LandingMarker,
+        pixel_art: &mut PixelArt,
+        pause_gesture: &mut PauseGesture,
+        response_curve: &mut ResponseCurve,
+        mirror_controls: &mut MirrorControls,
+        steady_speed: &mut SteadySpeed,
+        tap: Option<Vec2>,
+    ) -> bool {
+        // A touch drags the sliders, or the mouse while its button is held
+        let pointer = touches()
+            .first()
+            .map(|touch| touch.position)
+            .or_else(|| is_mouse_button_down(MouseButton::Left).then(|| mouse_position().into()))
+            .map(|position| game_area.screen_to_game(position));
+
+        if let Some(tap) = tap {
+            match self.page {
+                Page::General => {
+                    self.dragging = [Slider::Music, Slider::Effects]
+                        .into_iter()
+                        .find(|slider| slider.grab_area().contains(tap));
+                    if MUTE_TOGGLE.contains(tap) {
+                        sounds.toggle_mute();
+                    }
+                    if THEME_TOGGLE.contains(tap) {
+                        game_area.theme = game_area.theme.next();
+                        game_area.theme.save();
+                    }
+                    if LANG_TOGGLE.contains(tap) {
+                        game_area.lang = game_area.lang.next();
+                        game_area.lang.save();
+                    }
+                    if LAYOUT_TOGGLE.contains(tap) {
+                        game_area.layout = game_area.layout.next();
+                        game_area.layout.save();
+                    }
+                    if FrameLimiter::SUPPORTED && FPS_CAP_TOGGLE.contains(tap) {
+                        frame_limiter.toggle();
+                    }
+                    if Haptics::SUPPORTED && HAPTICS_TOGGLE.contains(tap) {
+                        haptics.toggle();
+                    }
+                    if PIXEL_ART_TOGGLE.contains(tap) {
+                        pixel_art.toggle();
+                    }
+                }
+                Page::Controls => {
+                    if RESPONSE_CURVE_TOGGLE.contains(tap) {
+                        *response_curve = response_curve.next();
+                        response_curve.save();
+                    }
+                    if MIRROR_CONTROLS_TOGGLE.contains(tap) {
+                        mirror_controls.toggle();
+                    }
+                    if PAUSE_GESTURE_TOGGLE.contains(tap) {
+                        pause_gesture.toggle();
+                    }
+                    if Tilt::SUPPORTED && TILT_TOGGLE.contains(tap) {
+                        tilt.toggle();
+                    }
+                    if tilt.is_enabled() && TILT_CALIBRATE_BUTTON.contains(tap) {
+                        tilt.calibrate();
+                    }
+                }
+                Page::Accessibility => {
+                    if STEADY_SPEED_TOGGLE.contains(tap) {
+                        steady_speed.toggle();
+                    }
+                    if LANDING_MARKER_TOGGLE.contains(tap) {
+                        landing_marker.toggle();
+                    }
+                }
+            }
+            if PAGE_BUTTON.contains(tap) {
+                self.page = self.page.next();
+            }
+        }
+
+        match (self.dragging, pointer) {
+            (Some(slider), Some(pointer)) => {
+                let track = slider.track();
+                let value = ((pointer.x - track.x) / track.w).clamp(0.0, 1.0);
+                match slider {
+                    Slider::Music => sounds.set_music_volume(value),
+                    Slider::Effects => sounds.set_effects_volume(value),
+                }
+            }
+            (Some(_), None) => {
+                // Only save once the slider is let go rather than every frame of the drag
+                self.dragging = None;
+                sounds.save_volumes();
+            }
+            (None, _) => {}
+        }
+
+        // Coming back to settings starts on the general page again
+        let back =
+            is_key_pressed(KeyCode::Escape) || tap.is_some_and(|tap| BACK_BUTTON.contains(tap));
+        if back {
+            self.page = Page::General;
+        }
+        back
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(
+        &self,
+        game_area: &GameArea,
+        sounds: &Sounds,
+        frame_limiter: &FrameLimiter,
+        haptics: &Haptics,
+        tilt: &Tilt,
+        landing_marker: &LandingMarker,
+        pixel_art: &PixelArt,
+        pause_gesture: &PauseGesture,
+        response_curve: ResponseCurve,
+        mirror_controls: &MirrorControls,
+        steady_speed: &SteadySpeed,
+    ) {
+        // Fade out the playfield like the game over screen does
+        draw_rectangle(
+            game_area.rect.x,
+            game_area.rect.y,
+            game_area.rect.w,
+            game_area.rect.h,
+            game_area.theme.overlay(0.6),
+        );
+        let lang = game_area.lang;
+        let text_color = game_area.theme.text();
+        let on_off = |on: bool| lang.get(if on { Text::On } else { Text::Off });
+        let draw_row = |text: &str, row: Rect| {
+            game_area.draw_centered_text(text, row.bottom() - 1.5, 6.0, text_color);
+        };
+        game_area.draw_centered_text(lang.get(self.page.title()), 9.0, 8.0, text_color);
+
+        match self.page {
+            Page::General => {
+                for (slider, label) in [
+                    (Slider::Music, Text::Music),
+                    (Slider::Effects, Text::Effects),
+                ] {
+                    draw_slider(game_area, slider, label, sounds);
+                }
+                draw_row(
+                    &format!("{}: {}", lang.get(Text::Sound), on_off(!sounds.is_muted())),
+                    MUTE_TOGGLE,
+                );
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::Theme),
+                        lang.get(game_area.theme.label())
+                    ),
+                    THEME_TOGGLE,
+                );
+                draw_row(
+                    &format!("{}: {}", lang.get(Text::Language), lang.name()),
+                    LANG_TOGGLE,
+                );
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::Paws),
+                        lang.get(game_area.layout.label())
+                    ),
+                    LAYOUT_TOGGLE,
+                );
+                if FrameLimiter::SUPPORTED {
+                    let fps_cap = if frame_limiter.is_enabled() {
+                        format!("{} FPS", FPS_CAP)
+                    } else {
+                        lang.get(Text::Off).to_string()
+                    };
+                    draw_row(
+                        &format!("{}: {}", lang.get(Text::FrameRateCap), fps_cap),
+                        FPS_CAP_TOGGLE,
+                    );
+                }
+                if Haptics::SUPPORTED {
+                    draw_row(
+                        &format!(
+                            "{}: {}",
+                            lang.get(Text::Vibration),
+                            on_off(haptics.is_enabled())
+                        ),
+                        HAPTICS_TOGGLE,
+                    );
+                }
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::PixelArt),
+                        on_off(pixel_art.is_enabled())
+                    ),
+                    PIXEL_ART_TOGGLE,
+                );
+            }
+            Page::Controls => {
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::PawResponse),
+                        lang.get(response_curve.label())
+                    ),
+                    RESPONSE_CURVE_TOGGLE,
+                );
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::MirrorControls),
+                        on_off(mirror_controls.is_enabled())
+                    ),
+                    MIRROR_CONTROLS_TOGGLE,
+                );
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::PauseGesture),
+                        on_off(pause_gesture.is_enabled())
+                    ),
+                    PAUSE_GESTURE_TOGGLE,
+                );
+                if Tilt::SUPPORTED {
+                    draw_row(
+                        &format!(
+                            "{}: {}",
+                            lang.get(Text::TiltSteering),
+                            on_off(tilt.is_enabled())
+                        ),
+                        TILT_TOGGLE,
+                    );
+                }
+                if tilt.is_enabled() {
+                    draw_row(lang.get(Text::CalibrateTilt), TILT_CALIBRATE_BUTTON);
+                }
+            }
+            Page::Accessibility => {
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::SteadySpeed),
+                        on_off(steady_speed.is_enabled())
+                    ),
+                    STEADY_SPEED_TOGGLE,
+                );
+                draw_row(
+                    &format!(
+                        "{}: {}",
+                        lang.get(Text::LandingMarker),
+                        on_off(landing_marker.is_enabled())
+                    ),
+                    LANDING_MARKER_TOGGLE,
+                );
+            }
+        }
+
+        // Names the page it goes to
+        draw_row(lang.get(self.page.next().title()), PAGE_BUTTON);
+        game_area.draw_centered_text(
+            lang.get(Text::Back),
+            BACK_BUTTON.bottom() - 2.5,
+            8.0,
+            text_color,
+        );
+    }
+}
+
+// The slider's label and value above its track, filled up to its handle
+fn draw_slider(game_area: &GameArea, slider: Slider, label: Text, sounds: &Sounds) {
+    let track = slider.track();
+    let value = slider.value(sounds);
+    game_area.draw_centered_text(
+        &format!(
+            "{}: {}%",
+            game_area.lang.get(label),
+            (value * 100.0).round()
+        ),
+        track.y - 4.0,
+        6.0,
+        game_area.theme.text(),
+    );
+    let top_left = game_area.game_to_screen(track.point(), TranslateType::Normal);
+    let size = game_area.game_to_screen(track.size(), TranslateType::JustScale);
+    draw_rectangle(top_left.x, top_left.y, size.x, size.y, DARKGRAY);
+    draw_rectangle(
+        top_left.x,
+        top_left.y,
+        size.x * value,
+        size.y,
+        game_area.theme.text(),
+    );
+    let handle = game_area.game_to_screen(
+        Vec2 {
+            x: track.x + track.w * value,
+            y: track.center().y,
+        },
+        TranslateType::Normal,
+    );
+    let handle_radius = game_area
+        .game_to_screen(
+            Vec2 {
+                x: SLIDER_HANDLE_RADIUS,
+                y: SLIDER_HANDLE_RADIUS,
+            },
+            TranslateType::JustScale,
+        )
+        .x;
+    draw_circle(handle.x, handle.y, handle_radius, game_area.theme.text());
+}
@@ -0,0 +1,44 @@
+use macroquad::prelude::*;
+
+// How slow time runs at the start of a close call, easing back to full speed over this many
+// real seconds
+const SLOW_MOTION_SCALE: f32 = 0.3;
+const SLOW_MOTION_DURATION: f32 = 0.5;
+
+// Briefly slows the game down after a last moment save
+pub struct SlowMotion {
+    // Real seconds left of the effect
+    remaining: f32,
+}
+
+impl SlowMotion {
+    pub fn new() -> Self {
+        Self { remaining: 0.0 }
+    }
+
+    // Does nothing while already slowed, so back to back saves can't keep time slow forever
+    pub fn trigger(&mut self) {
+        if self.remaining <= 0.0 {
+            self.remaining = SLOW_MOTION_DURATION;
+        }
+    }
+
+    // Counts down in real time rather than slowed time so it always wears off
+    pub fn update(&mut self) {
+        self.remaining = (self.remaining - get_frame_time()).max(0.0);
+    }
+
+    pub fn reset(&mut self) {
+        self.remaining = 0.0;
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        let progress = 1.0 - self.remaining / SLOW_MOTION_DURATION;
+        SLOW_MOTION_SCALE + (1.0 - SLOW_MOTION_SCALE) * progress
+    }
+
+    // This frame's time step for the game's physics
+    pub fn delta(&self) -> f32 {
+        get_frame_time() * self.time_scale()
+    }
+}
@@ -0,0 +1,275 @@
+use macroquad::audio::{play_sound, set_sound_volume, PlaySoundParams, Sound};
+use macroquad::prelude::*;
+
+use crate::game_area::{Drawable, GameArea, TranslateType};
+use crate::storage::{load_value, save_value};
+
+// The same sound effect won't play again within this many seconds
+const SOUND_COOLDOWN: f64 = 0.05;
+const MUSIC_VOLUME: f32 = 0.5;
+const EFFECTS_VOLUME: f32 = 1.0;
+// Zen mode turns the music and effects down to these fractions of their volumes
+const AMBIENT_MUSIC_SCALE: f32 = 0.5;
+const AMBIENT_EFFECTS_SCALE: f32 = 0.4;
+pub const MUTE_BUTTON: Rect = Rect {
+    x: 88.0,
+    y: 2.0,
+    w: 10.0,
+    h: 10.0,
+};
+
+const MUTED_KEY: &str = "muted";
+const MUSIC_VOLUME_KEY: &str = "music_volume";
+const EFFECTS_VOLUME_KEY: &str = "effects_volume";
+
+// Sounds that couldn't be loaded are None and stay silent
+struct SoundEffect {
+    sound: Option<Sound>,
+    last_played: f64,
+}
+
+impl SoundEffect {
+    fn new(sound: Option<Sound>) -> Self {
+        Self {
+            sound,
+            last_played: f64::NEG_INFINITY,
+        }
+    }
+
+    fn play(&mut self, volume: f32) {
+        // Rapid collisions would otherwise stack the same sound on top of itself
+        let Some(sound) = &self.sound else {
+            return;
+        };
+        if get_time() - self.last_played > SOUND_COOLDOWN {
+            play_sound(
+                sound,
+                PlaySoundParams {
+                    looped: false,
+                    volume,
+                },
+            );
+            self.last_played = get_time();
+        }
+    }
+}
+
+pub enum Effect {
+    Wall,
+    Paw,
+    Lose,
+    // Losing the ball with a combo going
+    ComboBreak,
+}
+
+pub struct Sounds {
+    wall: SoundEffect,
+    paw: SoundEffect,
+    lose: SoundEffect,
+    combo_break: SoundEffect,
+    music: Option<Sound>,
+    music_started: bool,
+    music_volume: f32,
+    effects_volume: f32,
+    muted: bool,
+    // While the game's paused the music holds its place silently and effects don't play
+    paused: bool,
+    // Turned down for zen mode
+    ambient: bool,
+}
+
+impl Sounds {
+    pub fn new(
+        wall: Option<Sound>,
+        paw: Option<Sound>,
+        lose: Option<Sound>,
+        combo_break: Option<Sound>,
+        music: Option<Sound>,
+    ) -> Self {
+        Self {
+            wall: SoundEffect::new(wall),
+            paw: SoundEffect::new(paw),
+            lose: SoundEffect::new(lose),
+            combo_break: SoundEffect::new(combo_break),
+            music,
+            music_started: false,
+            music_volume: load_volume(MUSIC_VOLUME_KEY, MUSIC_VOLUME),
+            effects_volume: load_volume(EFFECTS_VOLUME_KEY, EFFECTS_VOLUME),
+            muted: load_value(MUTED_KEY).is_some_and(|value| value == "true"),
+            paused: false,
+            ambient: false,
+        }
+    }
+
+    // A copy that never plays anything, for games going on in the background
+    pub fn silent(&self) -> Self {
+        Self {
+            wall: SoundEffect::new(self.wall.sound.clone()),
+            paw: SoundEffect::new(self.paw.sound.clone()),
+            lose: SoundEffect::new(self.lose.sound.clone()),
+            combo_break: SoundEffect::new(self.combo_break.sound.clone()),
+            music: self.music.clone(),
+            music_started: true,
+            music_volume: 0.0,
+            effects_volume: 0.0,
+            muted: true,
+            paused: false,
+            ambient: false,
+        }
+    }
+
+    pub fn play(&mut self, effect: Effect) {
+        if self.muted || self.paused {
+            return;
+        }
+        let volume = if self.ambient {
+            self.effects_volume * AMBIENT_EFFECTS_SCALE
+        } else {
+            self.effects_volume
+        };
+        match effect {
+            Effect::Wall => self.wall.play(volume),
+            Effect::Paw => self.paw.play(volume),
+            Effect::Lose => self.lose.play(volume),
+            Effect::ComboBreak => self.combo_break.play(volume),
+        }
+    }
+
+    // Starts the music the first time it's called, later calls leave it playing where it is
+    pub fn start_music(&mut self) {
+        if self.music_started {
+            return;
+        }
+        if let Some(music) = &self.music {
+            play_sound(
+                music,
+                PlaySoundParams {
+                    looped: true,
+                    volume: self.current_music_volume(),
+                },
+            );
+        }
+        self.music_started = true;
+    }
+
+    // Macroquad can only stop a sound and start it again from the top, so the music keeps playing
+    // silently while paused to pick up from where it was on resuming
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.update_music_volume();
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.update_music_volume();
+    }
+
+    pub fn set_ambient(&mut self, ambient: bool) {
+        self.ambient = ambient;
+        self.update_music_volume();
+    }
+
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+        self.update_music_volume();
+        save_value(MUTED_KEY, &self.muted.to_string());
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        self.music_volume
+    }
+
+    pub fn effects_volume(&self) -> f32 {
+        self.effects_volume
+    }
+
+    // Volumes go from 0 to 1 and aren't saved until save_volumes is called
+    pub fn set_music_volume(&mut self, volume: f32) {
+        self.music_volume = volume.clamp(0.0, 1.0);
+        self.update_music_volume();
+    }
+
+    pub fn set_effects_volume(&mut self, volume: f32) {
+        self.effects_volume = volume.clamp(0.0, 1.0);
+    }
+
+    pub fn save_volumes(&self) {
+        save_value(MUSIC_VOLUME_KEY, &self.music_volume.to_string());
+        save_value(EFFECTS_VOLUME_KEY, &self.effects_volume.to_string());
+    }
+
+    fn update_music_volume(&self) {
+        if let Some(music) = &self.music {
+            set_sound_volume(music, self.current_music_volume());
+        }
+    }
+
+    fn current_music_volume(&self) -> f32 {
+        if self.muted || self.paused {
+            0.0
+        } else if self.ambient {
+            self.music_volume * AMBIENT_MUSIC_SCALE
+        } else {
+            self.music_volume
+        }
+    }
+}
+
+// A saved volume, or `default` if there isn't a valid one
+fn load_volume(key: &str, default: f32) -> f32 {
+    load_value(key)
+        .and_then(|value| value.parse::<f32>().ok())
+        .filter(|volume| (0.0..=1.0).contains(volume))
+        .unwrap_or(default)
+}
+
+impl Drawable for Sounds {
+    fn draw(&self, game_area: &GameArea) {
+        // A little speaker, crossed out when muted
+        let game_to_screen =
+            |x: f32, y: f32| game_area.game_to_screen(Vec2 { x, y }, TranslateType::Normal);
+        let line_thickness = game_area
+            .game_to_screen(Vec2 { x: 0.8, y: 0.8 }, TranslateType::JustScale)
+            .x;
+        let button = MUTE_BUTTON;
+
+        let body_top_left = game_to_screen(button.x + 1.0, button.y + 3.5);
+        let body_bottom_right = game_to_screen(button.x + 3.5, button.y + 6.5);
+        draw_rectangle(
+            body_top_left.x,
+            body_top_left.y,
+            body_bottom_right.x - body_top_left.x,
+            body_bottom_right.y - body_top_left.y,
+            game_area.theme.text(),
+        );
+        draw_triangle(
+            game_to_screen(button.x + 2.0, button.y + 5.0),
+            game_to_screen(button.x + 6.0, button.y + 1.0),
+            game_to_screen(button.x + 6.0, button.y + 9.0),
+            game_area.theme.text(),
+        );
+
+        if self.muted {
+            let start = game_to_screen(button.x + 1.0, button.y + 1.0);
+            let end = game_to_screen(button.right() - 1.0, button.bottom() - 1.0);
+            draw_line(start.x, start.y, end.x, end.y, line_thickness, RED);
+        } else {
+            for (x, half_height) in [(7.5, 1.5), (9.0, 3.0)] {
+                let start = game_to_screen(button.x + x, button.y + 5.0 - half_height);
+                let end = game_to_screen(button.x + x, button.y + 5.0 + half_height);
+                draw_line(
+                    start.x,
+                    start.y,
+                    end.x,
+                    end.y,
+                    line_thickness,
+                    game_area.theme.text(),
+                );
+            }
+        }
+    }
+}
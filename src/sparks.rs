@@ -0,0 +1,85 @@
+use macroquad::prelude::*;
+
+use crate::game_area::{Drawable, GameArea, TranslateType};
+
+// Sparks thrown off each wall hit, flying at up to this many game units per second within this many
+// radians of straight out from the wall, and slowing down as they fade
+const SPARKS_PER_HIT: usize = 6;
+const SPARK_SPEED: f32 = 30.0;
+const SPARK_SPREAD: f32 = 1.0;
+const SPARK_DRAG: f32 = 4.0;
+const SPARK_LIFETIME: f32 = 0.3;
+const SPARK_SIZE: f32 = 0.8;
+// The oldest sparks make way once there are this many, however fast the hits come
+const MAX_SPARKS: usize = 120;
+
+struct Spark {
+    // In game units
+    position: Vec2,
+    velocity: Vec2,
+    age: f32,
+}
+
+// Little sparks flying off wherever the ball hits a wall
+pub struct Sparks {
+    sparks: Vec<Spark>,
+}
+
+impl Sparks {
+    pub fn new() -> Self {
+        Self { sparks: Vec::new() }
+    }
+
+    // `normal` points out of the wall, the way the sparks fly
+    pub fn spawn(&mut self, position: Vec2, normal: Vec2) {
+        for _ in 0..SPARKS_PER_HIT {
+            let angle = rand::gen_range(-SPARK_SPREAD, SPARK_SPREAD);
+            let speed = rand::gen_range(SPARK_SPEED * 0.3, SPARK_SPEED);
+            self.sparks.push(Spark {
+                position,
+                velocity: Vec2::from_angle(angle).rotate(normal) * speed,
+                age: 0.0,
+            });
+        }
+        let excess = self.sparks.len().saturating_sub(MAX_SPARKS);
+        self.sparks.drain(..excess);
+    }
+
+    pub fn update(&mut self, delta: f32) {
+        for spark in self.sparks.iter_mut() {
+            spark.age += delta;
+            spark.position += spark.velocity * delta;
+            spark.velocity *= (-SPARK_DRAG * delta).exp();
+        }
+        self.sparks.retain(|spark| spark.age < SPARK_LIFETIME);
+    }
+
+    pub fn reset(&mut self) {
+        self.sparks.clear();
+    }
+}
+
+impl Drawable for Sparks {
+    fn draw(&self, game_area: &GameArea) {
+        let size = game_area
+            .game_to_screen(
+                Vec2 {
+                    x: SPARK_SIZE,
+                    y: SPARK_SIZE,
+                },
+                TranslateType::JustScale,
+            )
+            .x;
+        for spark in &self.sparks {
+            let position = game_area.game_to_screen(spark.position, TranslateType::Normal);
+            let alpha = 1.0 - spark.age / SPARK_LIFETIME;
+            draw_rectangle(
+                position.x - size / 2.0,
+                position.y - size / 2.0,
+                size,
+                size,
+                Color { a: alpha, ..GOLD },
+            );
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::storage::{load_value, save_value};
+
+const STEADY_SPEED_KEY: &str = "steady_speed";
+
+// An accessibility option keeping the ball at its starting speed however high the score gets,
+// for players who can't keep up as it speeds up. Off unless it's been turned on in settings
+pub struct SteadySpeed {
+    enabled: bool,
+}
+
+impl SteadySpeed {
+    pub fn new() -> Self {
+        Self {
+            enabled: load_value(STEADY_SPEED_KEY).as_deref() == Some("true"),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(STEADY_SPEED_KEY, &self.enabled.to_string());
+    }
+}
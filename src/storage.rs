@@ -0,0 +1,129 @@
+#[cfg(not(target_arch = "wasm32"))]
+use macroquad::prelude::*;
+#[cfg(target_arch = "wasm32")]
+use sapp_jsutils::{JsObject, JsObjectWeak};
+
+const SAVE_NAME: &str = "cat_ball_wow_mq";
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_FILE_NAME: &str = "save.txt";
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_storage_get(key: JsObjectWeak) -> JsObject;
+    fn cat_ball_wow_storage_set(key: JsObjectWeak, value: JsObjectWeak);
+}
+
+// On the web values are kept in localStorage, elsewhere in a key=value file in the OS data directory
+#[cfg(target_arch = "wasm32")]
+pub fn load_value(key: &str) -> Option<String> {
+    let key = JsObject::string(&format!("{SAVE_NAME}.{key}"));
+    let value = unsafe { cat_ball_wow_storage_get(key.weak()) };
+    if value.is_nil() {
+        return None;
+    }
+    let mut result = String::new();
+    value.to_string(&mut result);
+    Some(result)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_value(key: &str, value: &str) {
+    let key = JsObject::string(&format!("{SAVE_NAME}.{key}"));
+    let value = JsObject::string(value);
+    unsafe { cat_ball_wow_storage_set(key.weak(), value.weak()) };
+}
+
+// Documents are values too big for one line, like JSON. On the web they're kept in localStorage
+// like any other value, elsewhere each one gets its own file named `name` next to the save file
+#[cfg(target_arch = "wasm32")]
+pub fn load_document(name: &str) -> Option<String> {
+    load_value(name)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_document(name: &str, contents: &str) {
+    save_value(name, contents);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_document(name: &str) -> Option<String> {
+    save_directory().and_then(|directory| std::fs::read_to_string(directory.join(name)).ok())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_document(name: &str, contents: &str) {
+    let Some(directory) = save_directory() else {
+        warn!("No data directory found, {} will not be saved", name);
+        return;
+    };
+    let path = directory.join(name);
+    let result = std::fs::create_dir_all(&directory).and_then(|_| std::fs::write(&path, contents));
+    if let Err(error) = result {
+        warn!("Could not save {}: {}", path.display(), error);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_directory() -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
+
+    let data_directory = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    data_directory.map(|directory| directory.join(SAVE_NAME))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_file_path() -> Option<std::path::PathBuf> {
+    save_directory().map(|directory| directory.join(SAVE_FILE_NAME))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_save_file() -> Vec<(String, String)> {
+    let Some(contents) = save_file_path().and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_value(key: &str) -> Option<String> {
+    read_save_file()
+        .into_iter()
+        .find(|(saved_key, _)| saved_key == key)
+        .map(|(_, value)| value)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_value(key: &str, value: &str) {
+    let Some(path) = save_file_path() else {
+        warn!("No data directory found, {} will not be saved", key);
+        return;
+    };
+    let mut values = read_save_file();
+    values.retain(|(saved_key, _)| saved_key != key);
+    values.push((key.to_string(), value.to_string()));
+    let contents: String = values
+        .iter()
+        .map(|(key, value)| format!("{key}={value}\n"))
+        .collect();
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, contents));
+    if let Err(error) = result {
+        warn!("Could not save {} to {}: {}", key, path.display(), error);
+    }
+}
@@ -0,0 +1,153 @@
+use macroquad::prelude::*;
+
+use crate::lang::Text;
+use crate::storage::{load_value, save_value};
+
+const THEME_KEY: &str = "theme";
+
+// Colors everything is drawn with, the high contrast one keeps the ball and paws bright on a dark
+// field for players who struggle to tell them apart from the pink
+#[derive(Clone, Copy, PartialEq)]
+pub enum Theme {
+    Default,
+    HighContrast,
+}
+
+impl Theme {
+    const ALL: [Theme; 2] = [Theme::Default, Theme::HighContrast];
+
+    // The last chosen theme, or the default if there isn't a saved one
+    pub fn load() -> Self {
+        let saved = load_value(THEME_KEY);
+        Self::ALL
+            .into_iter()
+            .find(|theme| saved.as_deref() == Some(theme.key()))
+            .unwrap_or(Theme::Default)
+    }
+
+    pub fn save(&self) {
+        save_value(THEME_KEY, self.key());
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn label(&self) -> Text {
+        match self {
+            Theme::Default => Text::DefaultTheme,
+            Theme::HighContrast => Text::HighContrastTheme,
+        }
+    }
+
+    // The theme after this one, wrapping back round to the first
+    pub fn next(&self) -> Self {
+        match self {
+            Theme::Default => Theme::HighContrast,
+            Theme::HighContrast => Theme::Default,
+        }
+    }
+
+    // Behind the playfield while it's being drawn
+    pub fn background(&self) -> Color {
+        match self {
+            Theme::Default => PINK,
+            Theme::HighContrast => BLACK,
+        }
+    }
+
+    // The playfield's texture is multiplied by this
+    pub fn field_tint(&self) -> Color {
+        match self {
+            Theme::Default => WHITE,
+            Theme::HighContrast => Color::new(0.15, 0.15, 0.15, 1.0),
+        }
+    }
+
+    // What the field is tinted towards as the score climbs
+    pub fn heat_tint(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(1.0, 0.55, 0.45, 1.0),
+            Theme::HighContrast => Color::new(0.35, 0.08, 0.08, 1.0),
+        }
+    }
+
+    // The warning along the paws' edge when a ball's about to be lost
+    pub fn danger(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(0.9, 0.1, 0.1, 1.0),
+            Theme::HighContrast => Color::new(1.0, 0.25, 0.25, 1.0),
+        }
+    }
+
+    // Washed over the field in zen mode to calm its colors down
+    pub fn zen_wash(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(0.6, 0.8, 0.95, 0.3),
+            Theme::HighContrast => Color::new(0.15, 0.25, 0.45, 0.35),
+        }
+    }
+
+    // Strands of safety net along the paws' edge
+    pub fn safety_net(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(0.2, 0.65, 0.35, 0.7),
+            Theme::HighContrast => Color::new(0.3, 1.0, 0.5, 0.9),
+        }
+    }
+
+    pub fn text(&self) -> Color {
+        match self {
+            Theme::Default => BLACK,
+            Theme::HighContrast => WHITE,
+        }
+    }
+
+    // Drawn behind the score so it stands out from anything in the background
+    pub fn text_shadow(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(1.0, 1.0, 1.0, 0.8),
+            Theme::HighContrast => BLACK,
+        }
+    }
+
+    // Drawn over the playfield behind menus to make their text stand out
+    pub fn overlay(&self, alpha: f32) -> Color {
+        match self {
+            Theme::Default => Color::new(1.0, 1.0, 1.0, alpha),
+            Theme::HighContrast => Color::new(0.0, 0.0, 0.0, alpha),
+        }
+    }
+
+    // The edges the ball bounces off
+    pub fn wall(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(0.35, 0.2, 0.25, 0.6),
+            Theme::HighContrast => Color::new(1.0, 1.0, 1.0, 0.8),
+        }
+    }
+
+    pub fn center_line(&self) -> Color {
+        match self {
+            Theme::Default => Color::new(0.0, 0.0, 0.0, 0.15),
+            Theme::HighContrast => Color::new(1.0, 1.0, 1.0, 0.4),
+        }
+    }
+
+    pub fn ball_tint(&self) -> Color {
+        match self {
+            Theme::Default => WHITE,
+            Theme::HighContrast => YELLOW,
+        }
+    }
+
+    pub fn paw_tint(&self) -> Color {
+        match self {
+            Theme::Default => WHITE,
+            Theme::HighContrast => Color::new(0.4, 0.9, 1.0, 1.0),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::storage::{load_value, save_value};
+
+const TILT_KEY: &str = "tilt";
+const TILT_NEUTRAL_KEY: &str = "tilt_neutral";
+// Tilting this many degrees away from neutral steers the paws all the way to one side
+const MAX_TILT: f32 = 25.0;
+
+#[cfg(target_arch = "wasm32")]
+extern "C" {
+    fn cat_ball_wow_tilt() -> f32;
+}
+
+// Steering both paws by tilting the device, through deviceorientation on the web and not
+// available elsewhere. Off unless it's been turned on in settings
+pub struct Tilt {
+    enabled: bool,
+    // The reading, in degrees, that counts as holding the device level
+    neutral: f32,
+}
+
+impl Tilt {
+    // Whether tilting does anything on this platform
+    pub const SUPPORTED: bool = cfg!(target_arch = "wasm32");
+
+    pub fn new() -> Self {
+        Self {
+            enabled: load_value(TILT_KEY).as_deref() == Some("true"),
+            neutral: load_value(TILT_NEUTRAL_KEY)
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0.0),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // Turning it on calibrates too, as however the device is held then is likely how it'll be
+    // held while playing
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        save_value(TILT_KEY, &self.enabled.to_string());
+        if self.enabled {
+            self.calibrate();
+        }
+    }
+
+    // Takes how the device is held now as level
+    pub fn calibrate(&mut self) {
+        if let Some(reading) = reading() {
+            self.neutral = reading;
+            save_value(TILT_NEUTRAL_KEY, &self.neutral.to_string());
+        }
+    }
+
+    // How far the device is tilted from neutral, from -1 (all the way left) to 1 (all the way right),
+    // or nothing when tilt steering is off or the device hasn't reported a tilt
+    pub fn steering(&self) -> Option<f32> {
+        if !self.enabled {
+            return None;
+        }
+        reading().map(|reading| ((reading - self.neutral) / MAX_TILT).clamp(-1.0, 1.0))
+    }
+}
+
+// The device's left to right tilt in degrees, the browser reports NaN until it's had a reading
+#[cfg(target_arch = "wasm32")]
+fn reading() -> Option<f32> {
+    let reading = unsafe { cat_ball_wow_tilt() };
+    (!reading.is_nan()).then_some(reading)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn reading() -> Option<f32> {
+    None
+}